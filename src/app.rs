@@ -1,17 +1,24 @@
 pub mod commands;
 pub mod events;
 pub mod input;
+pub mod log;
 pub mod parser;
 
 use crate::app::{commands::Command as AppCommand, events::Event as AppEvent};
-use crate::engine::{Engine, commands::Command as EngineCommand};
+use crate::domain::Position;
+use crate::engine::{
+    Engine, Event as EngineEvent, commands::Command as EngineCommand, rules::RuleSet,
+    timer::Event as TimerEvent,
+};
+use crate::export;
+use crate::scoring::{FinalScores, Leaderboard, ScoringTable, SeasonStanding};
 use crate::snapshot::{self, Snapshot};
-use crate::storage::timestamped_save_path;
+use crate::stats::{PlayerStats, StatsIndex};
+use crate::storage::{self, timestamped_save_path};
 use clap::Parser;
 use input::{InputMode, PopupKind};
+use log::{EventLog, Record};
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
-use std::fs::File;
-use std::io::Write;
 use std::path::Path;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
@@ -24,14 +31,28 @@ pub enum AppStatus {
 
 pub struct App {
     pub engine: Engine,
+    pub rules: RuleSet,
+    pub scoring: ScoringTable,
+    pub season: String,
     pub status: AppStatus,
     pub input: String,
     pub input_mode: InputMode,
 
     pub events: Vec<AppEvent>,
+    /// Every event of the current game, with timestamps
+    pub log: EventLog,
     pub current_timer: Option<u64>,
+    /// Player statistics shown instead of the table, if requested
+    pub stats: Option<PlayerStats>,
+    /// Scores of the game that just ended, shown until dismissed
+    pub final_scores: Option<FinalScores>,
+    /// Night action sent from a seat's device, applied on `confirm`
+    pub pending_action: Option<(Position, EngineCommand)>,
     pub event_tx: mpsc::Sender<AppEvent>,
     pub timer_task: Option<JoinHandle<()>>,
+    /// Bumped whenever the countdown is replaced, so a `TurnTimeUp` sent by
+    /// an earlier one is recognised as stale
+    pub timer_generation: u64,
 }
 
 impl Snapshot for App {
@@ -44,31 +65,57 @@ impl Snapshot for App {
             input_mode: self.input_mode.clone(),
             current_timer: self.current_timer,
             events: self.events.clone(),
+            stats: self.stats.clone(),
+            final_scores: self.final_scores.clone(),
+            allowed: self.engine.allowed_commands(),
         }
     }
 }
 
 impl App {
-    pub fn new(event_tx: mpsc::Sender<AppEvent>) -> Self {
+    pub fn new(
+        event_tx: mpsc::Sender<AppEvent>,
+        rules: RuleSet,
+        scoring: ScoringTable,
+        season: String,
+    ) -> Self {
         App {
-            engine: Engine::new(),
+            engine: Engine::new(rules.clone()),
+            rules,
+            scoring,
+            season,
             status: AppStatus::Running,
             input: String::new(),
             input_mode: InputMode::Normal,
             events: Vec::new(),
+            log: EventLog::new(storage::timestamped_log_path()),
             current_timer: None,
+            stats: None,
+            final_scores: None,
+            pending_action: None,
             event_tx,
             timer_task: None,
+            timer_generation: 0,
         }
     }
 
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
-        let snapshot = self.snapshot();
+        storage::save_engine(&self.engine, self.log.entries(), path)
+    }
 
-        let json = serde_json::to_string_pretty(&snapshot.engine.game)?;
+    pub fn export_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: Option<export::Format>,
+    ) -> anyhow::Result<()> {
+        storage::export_engine(&self.engine, path, format)
+    }
 
-        let mut file = File::create(path)?;
-        file.write_all(json.as_bytes())?;
+    pub fn load_from_file<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
+        let (engine, log) = storage::load_save(path)?;
+        self.engine = engine;
+        self.log.reset(log);
+        self.stop_timer();
 
         Ok(())
     }
@@ -117,7 +164,11 @@ impl App {
         match kind {
             Join => {
                 if !value.is_empty() {
-                    self.handle_command(AppCommand::Join { name: value }).await;
+                    self.handle_command(AppCommand::Join {
+                        name: value,
+                        seat: None,
+                    })
+                    .await;
                 }
             }
 
@@ -274,6 +325,14 @@ impl App {
                 self.input.clear();
             }
 
+            KeyCode::Esc if self.stats.is_some() => {
+                self.stats = None;
+            }
+
+            KeyCode::Esc if self.final_scores.is_some() => {
+                self.final_scores = None;
+            }
+
             KeyCode::Esc => {
                 self.status = AppStatus::Quit;
             }
@@ -309,6 +368,16 @@ impl App {
     pub async fn handle_command(&mut self, cmd: AppCommand) {
         use AppCommand::*;
 
+        // Same mapping as `mafia run`, so the TUI and scripts cannot drift
+        let cmd = match EngineCommand::try_from(cmd) {
+            Ok(command) => {
+                let result = self.engine.apply(command);
+                self.handle_engine_result(result).await;
+                return;
+            }
+            Err(cmd) => cmd,
+        };
+
         match cmd {
             Quit => {
                 let _ = self.event_tx.send(AppEvent::QuitRequested).await;
@@ -322,10 +391,37 @@ impl App {
                     let _ = self.event_tx.send(AppEvent::Error(err.to_string())).await;
                 }
             },
+            Load { file_name } => match self.load_from_file(&file_name) {
+                Ok(_) => {
+                    let _ = self.event_tx.send(AppEvent::Loaded(file_name)).await;
+                }
+                Err(err) => {
+                    let _ = self
+                        .event_tx
+                        .send(AppEvent::Error(format!(
+                            "Failed to load game from {file_name:?}: {err}"
+                        )))
+                        .await;
+                }
+            },
+            Export { file_name, format } => match self.export_to_file(&file_name, format) {
+                Ok(_) => {
+                    let _ = self.event_tx.send(AppEvent::Exported(file_name)).await;
+                }
+                Err(err) => {
+                    let _ = self
+                        .event_tx
+                        .send(AppEvent::Error(format!(
+                            "Failed to export game to {file_name:?}: {err}"
+                        )))
+                        .await;
+                }
+            },
             Timer { seconds } => {
                 if let Some(task) = self.timer_task.take() {
                     task.abort();
                 }
+                self.timer_generation += 1;
                 let tx = self.event_tx.clone();
                 let handle = tokio::spawn(async move {
                     let _ = tx.send(AppEvent::TimerStarted(seconds)).await;
@@ -338,135 +434,88 @@ impl App {
 
                 self.timer_task = Some(handle);
             }
-            Join { name } => {
-                let result = self.engine.apply(EngineCommand::Join { name });
-                self.handle_engine_result(result).await;
-            }
-            Leave { name } => {
-                let result = self.engine.apply(EngineCommand::Leave { name });
-                self.handle_engine_result(result).await;
-            }
-
-            Start => {
-                let result = self.engine.apply(EngineCommand::Start);
-                self.handle_engine_result(result).await;
-            }
-
-            Next => {
-                let result = self.engine.apply(EngineCommand::Advance);
+            Pause => {
+                let remaining = self.current_timer.unwrap_or_default();
+                let result = self.engine.apply(EngineCommand::PauseTimer { remaining });
                 self.handle_engine_result(result).await;
             }
 
-            AssignRole => {
-                let results = self.engine.apply(EngineCommand::AssignRole);
-                self.handle_engine_result(results).await;
-            }
-
-            RevokeRole => {
-                let results = self.engine.apply(EngineCommand::RevokeRole);
-                self.handle_engine_result(results).await;
-            }
-
-            Warn { position } => {
-                let results = self.engine.apply(EngineCommand::Warn {
-                    target: position.into(),
-                });
-                self.handle_engine_result(results).await;
-            }
-
-            Pardon { position } => {
-                let results = self.engine.apply(EngineCommand::Pardon {
-                    target: position.into(),
-                });
-                self.handle_engine_result(results).await;
-            }
-
-            Nominate { position } => {
-                let results = self.engine.apply(EngineCommand::Nominate {
-                    target: position.into(),
-                });
-                self.handle_engine_result(results).await;
-            }
-
-            Vote { positions } => {
-                let mut targets = Vec::new();
-                for pos in positions {
-                    targets.push(pos.into());
+            Confirm => match self.pending_action.take() {
+                Some((seat, command)) => {
+                    let result = self
+                        .engine
+                        .check_seat_action(seat, &command)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|_| self.engine.apply(command));
+                    self.handle_engine_result(result).await;
                 }
-
-                match self.engine.apply(EngineCommand::Vote { targets }) {
-                    Ok(events) => {
-                        for event in events {
-                            let _ = self.event_tx.send(AppEvent::Engine(event)).await;
-                        }
-                    }
-                    Err(err) => {
-                        let _ = self.event_tx.send(AppEvent::Error(err.to_string())).await;
-                    }
+                None => {
+                    let _ = self
+                        .event_tx
+                        .send(AppEvent::Error("No seat action to confirm".to_string()))
+                        .await;
                 }
-            }
-
-            Shoot { position } => {
-                let results = self.engine.apply(EngineCommand::Shoot {
-                    target: position.into(),
-                });
-                self.handle_engine_result(results).await;
-            }
-
-            Check { position } => {
-                let results = self.engine.apply(EngineCommand::Check {
-                    target: position.into(),
-                });
-                self.handle_engine_result(results).await;
-            }
+            },
 
-            Guess { targets } => {
-                let mut positions = Vec::new();
-                for target in targets {
-                    positions.push(target.into());
-                }
+            Stats { name: None } => self.stats = None,
 
-                match self
-                    .engine
-                    .apply(EngineCommand::Guess { targets: positions })
-                {
-                    Ok(events) => {
-                        for event in events {
-                            let _ = self.event_tx.send(AppEvent::Engine(event)).await;
-                        }
+            Stats { name: Some(name) } => {
+                match StatsIndex::new(storage::stats_index_path()).player(&name) {
+                    Ok(Some(stats)) => self.stats = Some(stats),
+                    Ok(None) => {
+                        let _ = self
+                            .event_tx
+                            .send(AppEvent::Error(format!("No finished games for {name}")))
+                            .await;
                     }
                     Err(err) => {
-                        let _ = self.event_tx.send(AppEvent::Error(err.to_string())).await;
+                        let _ = self
+                            .event_tx
+                            .send(AppEvent::Error(format!("Failed to read statistics: {err}")))
+                            .await;
                     }
                 }
             }
 
-            Assign { command } => {
-                let command = command.unwrap_or(commands::AssignCommand::Role { role: None });
-
-                let result = match command {
-                    commands::AssignCommand::Player { name } => {
-                        self.engine.apply(EngineCommand::Join { name })
-                    }
-                    commands::AssignCommand::Role { role } => {
-                        if role.is_none() {
-                            self.engine.apply(EngineCommand::AssignRole)
-                        } else {
-                            Ok(vec![])
-                        }
-                    }
-                };
-
-                match result {
-                    Ok(events) => {
-                        for event in events {
-                            let _ = self.event_tx.send(AppEvent::Engine(event)).await;
-                        }
-                    }
-                    Err(err) => {
-                        let _ = self.event_tx.send(AppEvent::Error(err.to_string())).await;
+            Undo => match self.engine.undo() {
+                Ok(events) => {
+                    self.stop_timer();
+                    for event in events.into_iter().rev() {
+                        self.record(Record::Reverted(event.clone()));
+                        let _ = self.event_tx.send(AppEvent::Reverted(event)).await;
                     }
                 }
+                Err(err) => {
+                    let _ = self.event_tx.send(AppEvent::Error(err.to_string())).await;
+                }
+            },
+
+            Redo => {
+                let result = self.engine.redo();
+                self.handle_engine_result(result).await;
+            }
+
+            Join { .. }
+            | Leave { .. }
+            | Table { .. }
+            | Swap { .. }
+            | Start
+            | Next
+            | AssignRole
+            | RevokeRole
+            | Warn { .. }
+            | Pardon { .. }
+            | Nominate { .. }
+            | Vote { .. }
+            | Shoot { .. }
+            | Check { .. }
+            | Heal { .. }
+            | Block { .. }
+            | Guess { .. }
+            | Resume
+            | Extend { .. }
+            | Assign { .. } => {
+                unreachable!("engine commands are applied above")
             }
         }
     }
@@ -491,13 +540,62 @@ impl App {
         self.input.clear();
     }
 
+    /// Holds a seat's night action for the host to confirm, if the engine
+    /// accepts it from that seat right now.
+    pub fn receive_seat_action(&mut self, seat: Position, command: EngineCommand) {
+        match self.engine.check_seat_action(seat, &command) {
+            Ok(()) => {
+                self.pending_action = Some((seat, command.clone()));
+                self.events.push(AppEvent::SeatAction { seat, command });
+            }
+            Err(err) => self.events.push(AppEvent::Error(format!(
+                "Rejected action from {seat}: {err}"
+            ))),
+        }
+    }
+
+    /// Scores the finished game and adds it to the season leaderboard.
+    fn score_game(&self) -> anyhow::Result<Option<FinalScores>> {
+        let Some(result) = self.engine.result else {
+            return Ok(None);
+        };
+        let scores = self.scoring.score(&self.engine.game.snapshot(), &result);
+
+        let path = storage::leaderboard_path(&self.season);
+        let mut leaderboard = Leaderboard::load(&path, &self.season)?;
+        leaderboard.record(&scores);
+        leaderboard.save(&path)?;
+
+        let standings = leaderboard.standings();
+        let season_standings = scores
+            .iter()
+            .filter_map(|s| {
+                let index = standings.iter().position(|(name, _)| *name == s.name)?;
+                let standing = SeasonStanding {
+                    rank: index + 1,
+                    points: standings[index].1.points,
+                };
+                Some((s.name.clone(), standing))
+            })
+            .collect();
+
+        Ok(Some(FinalScores {
+            result,
+            season: self.season.clone(),
+            scores,
+            season_standings,
+        }))
+    }
+
     async fn start_timer(&mut self, seconds: u64) {
         // stop previous timer if any
         if let Some(task) = self.timer_task.take() {
             task.abort();
         }
+        self.timer_generation += 1;
 
         let tx = self.event_tx.clone();
+        let generation = self.timer_generation;
 
         let handle = tokio::spawn(async move {
             let _ = tx.send(AppEvent::TimerStarted(seconds)).await;
@@ -507,33 +605,102 @@ impl App {
                 let _ = tx.send(AppEvent::TimerTick(remaining)).await;
             }
 
-            let _ = tx.send(AppEvent::TimerEnded).await;
+            let _ = tx.send(AppEvent::TurnTimeUp(generation)).await;
         });
 
         self.current_timer = Some(seconds);
         self.timer_task = Some(handle);
     }
 
+    fn stop_timer(&mut self) {
+        if let Some(task) = self.timer_task.take() {
+            task.abort();
+        }
+        self.timer_generation += 1;
+        self.current_timer = None;
+    }
+
+    /// Adds an app-level event to the log. Engine events are logged as
+    /// they are applied.
+    pub fn record_app_event(&mut self, event: &AppEvent) {
+        match event {
+            AppEvent::Key(_)
+            | AppEvent::TimerTick(_)
+            | AppEvent::Engine(_)
+            | AppEvent::Reverted(_)
+            | AppEvent::QuitRequested => {}
+            // A stale countdown is ignored, so it leaves no trace either
+            AppEvent::TurnTimeUp(generation) if *generation != self.timer_generation => {}
+            event => self.record(Record::App(event.to_string())),
+        }
+    }
+
+    fn record(&mut self, record: Record) {
+        if let Err(err) = self.log.record(&self.engine, record) {
+            self.events.push(AppEvent::Error(format!(
+                "Failed to write the event log: {err}"
+            )));
+        }
+    }
+
+    /// Tells the engine the turn clock ran out, unless `generation` belongs
+    /// to a countdown that has since been replaced or stopped.
+    pub async fn turn_time_up(&mut self, generation: u64) {
+        if generation != self.timer_generation {
+            return;
+        }
+        self.timer_task = None;
+        self.current_timer = None;
+        let result = self.engine.apply(EngineCommand::ExpireTimer);
+        self.handle_engine_result(result).await;
+    }
+
+    /// Keeps the countdown in step with the engine's turn clock.
+    async fn follow_timer(&mut self, event: &TimerEvent) {
+        match *event {
+            TimerEvent::Started { seconds, .. } => self.start_timer(seconds).await,
+            TimerEvent::Resumed { remaining } => self.start_timer(remaining).await,
+            TimerEvent::Paused { remaining } => {
+                self.stop_timer();
+                self.current_timer = Some(remaining);
+            }
+            TimerEvent::Extended { seconds } => {
+                let remaining = self.current_timer.unwrap_or_default() + seconds;
+                if self.engine.timer().is_some_and(|t| t.is_paused()) {
+                    self.current_timer = Some(remaining);
+                } else {
+                    self.start_timer(remaining).await;
+                }
+            }
+            TimerEvent::Expired { .. } => self.stop_timer(),
+        }
+    }
+
     async fn handle_engine_result(&mut self, result: anyhow::Result<Vec<crate::engine::Event>>) {
         match result {
             Ok(events) => {
+                // A turn without a clock stops the previous countdown
+                let new_turn = events.iter().any(|e| {
+                    matches!(
+                        e,
+                        EngineEvent::ActorAdvanced { .. } | EngineEvent::PhaseAdvanced { .. }
+                    )
+                });
+                if new_turn && self.engine.timer().is_none() {
+                    self.stop_timer();
+                }
+
                 for event in events {
                     // forward event
+                    self.record(Record::Engine(event.clone()));
                     let _ = self.event_tx.send(AppEvent::Engine(event.clone())).await;
 
-                    // AUTO TIMER HOOK
-                    if let crate::engine::Event::ActorAdvanced { .. } = event {
-                        if let Some(seconds) = timer_for_engine(&self.engine) {
-                            self.start_timer(seconds).await;
-                        }
+                    if let EngineEvent::Timer(event) = &event {
+                        self.follow_timer(event).await;
                     }
 
-                    if let crate::engine::Event::GameEnded = event {
-                        // stop any running timer
-                        if let Some(task) = self.timer_task.take() {
-                            task.abort();
-                        }
-                        self.current_timer = None;
+                    if let EngineEvent::GameEnded { .. } = event {
+                        self.stop_timer();
                         let path = timestamped_save_path();
 
                         match self.save_to_file(&path) {
@@ -549,7 +716,27 @@ impl App {
                                     .await;
                             }
                         }
-                        self.engine = Engine::new();
+                        if let Err(err) = StatsIndex::new(storage::stats_index_path())
+                            .ingest(&self.engine.snapshot())
+                        {
+                            let _ = self
+                                .event_tx
+                                .send(AppEvent::Error(format!(
+                                    "Failed to record statistics: {err}"
+                                )))
+                                .await;
+                        }
+                        match self.score_game() {
+                            Ok(scores) => self.final_scores = scores,
+                            Err(err) => {
+                                let _ = self
+                                    .event_tx
+                                    .send(AppEvent::Error(format!("Failed to score game: {err}")))
+                                    .await;
+                            }
+                        }
+                        self.engine = Engine::new(self.rules.clone());
+                        self.log.reset(Vec::new());
                     }
                 }
             }
@@ -559,33 +746,3 @@ impl App {
         }
     }
 }
-
-fn timer_for_engine(engine: &Engine) -> Option<u64> {
-    use crate::domain::EngineState::Game;
-    use crate::domain::{
-        Activity::*, EveningActivity::*, MorningActivity::*, NightActivity::*, NoonActivity::*,
-    };
-
-    match engine.state {
-        Game(Night(RoleAssignment)) => None,
-        Game(Night(SheriffReveal)) => Some(5),
-        Game(Night(DonReveal)) => Some(5),
-        Game(Night(MafiaBriefing)) => Some(60),
-        Game(Night(MafiaShooting)) => None,
-        Game(Night(SheriffCheck)) => Some(10),
-        Game(Night(DonCheck)) => Some(10),
-
-        Game(Morning(Guessing)) => Some(15),
-        Game(Morning(DeathSpeech)) => Some(60),
-
-        Game(Noon(Discussion)) => Some(60),
-
-        Game(Evening(Voting)) => Some(2),
-        Game(Evening(TieDiscussion)) => Some(30),
-        Game(Evening(TieVoting)) => Some(2),
-        Game(Evening(FinalVoting)) => Some(2),
-        Game(Evening(FinalSpeech)) => Some(60),
-
-        _ => None,
-    }
-}
@@ -1,6 +1,7 @@
 use clap::{Parser, Subcommand};
 
 use crate::domain::role::Role;
+use crate::engine::commands::Command as EngineCommand;
 
 /// All user-facing commands
 #[derive(Debug, Parser)]
@@ -8,10 +9,20 @@ pub enum Command {
     // app level commands to map to engine commands
     Join {
         name: String,
+        /// Seat the player at a pre-drawn position
+        #[arg(long)]
+        seat: Option<u8>,
     },
     Leave {
         name: String,
     },
+    Table {
+        seats: u8,
+    },
+    Swap {
+        from: u8,
+        to: u8,
+    },
     Start,
     Next,
     AssignRole,
@@ -36,6 +47,13 @@ pub enum Command {
     Check {
         position: u8,
     },
+    Heal {
+        position: u8,
+    },
+    /// Block a player for the rest of the night (Prostitute)
+    Block {
+        position: u8,
+    },
     Guess {
         targets: Vec<u8>,
     },
@@ -44,6 +62,20 @@ pub enum Command {
         #[command(subcommand)]
         command: Option<AssignCommand>,
     },
+    /// Apply the night action last sent from a seat's device
+    Confirm,
+
+    Undo,
+    Redo,
+
+    /// Pause the clock of the current turn
+    Pause,
+    /// Resume a paused turn clock
+    Resume,
+    /// Give the current turn extra seconds
+    Extend {
+        seconds: u64,
+    },
 
     // app lelvel commands
     Timer {
@@ -52,6 +84,19 @@ pub enum Command {
     End {
         file_name: String,
     },
+    Load {
+        file_name: String,
+    },
+    /// Write the game protocol; the format defaults to the file extension
+    Export {
+        file_name: String,
+        #[arg(long, value_enum)]
+        format: Option<crate::export::Format>,
+    },
+    /// Show a player's statistics; without a name, close them
+    Stats {
+        name: Option<String>,
+    },
     Quit,
 }
 
@@ -71,3 +116,68 @@ pub enum AssignCommand {
         role: Option<Role>,
     },
 }
+
+/// Host commands that map directly onto the engine. App-level commands
+/// (timers, saving, undo, ...) are handed back unchanged.
+impl TryFrom<Command> for EngineCommand {
+    type Error = Command;
+
+    fn try_from(cmd: Command) -> Result<Self, Self::Error> {
+        let engine_cmd = match cmd {
+            Command::Join { name, seat } => EngineCommand::Join {
+                name,
+                seat: seat.map(Into::into),
+            },
+            Command::Leave { name } => EngineCommand::Leave { name },
+            Command::Table { seats } => EngineCommand::ResizeTable { seats },
+            Command::Swap { from, to } => EngineCommand::SwapSeats {
+                from: from.into(),
+                to: to.into(),
+            },
+            Command::Start => EngineCommand::Start,
+            Command::Next => EngineCommand::Advance,
+            Command::AssignRole => EngineCommand::AssignRole,
+            Command::RevokeRole => EngineCommand::RevokeRole,
+            Command::Warn { position } => EngineCommand::Warn {
+                target: position.into(),
+            },
+            Command::Pardon { position } => EngineCommand::Pardon {
+                target: position.into(),
+            },
+            Command::Nominate { position } => EngineCommand::Nominate {
+                target: position.into(),
+            },
+            Command::Vote { positions } => EngineCommand::Vote {
+                targets: positions.into_iter().map(Into::into).collect(),
+            },
+            Command::Shoot { position } => EngineCommand::Shoot {
+                target: position.into(),
+            },
+            Command::Check { position } => EngineCommand::Check {
+                target: position.into(),
+            },
+            Command::Heal { position } => EngineCommand::Heal {
+                target: position.into(),
+            },
+            Command::Block { position } => EngineCommand::Block {
+                target: position.into(),
+            },
+            Command::Guess { targets } => EngineCommand::Guess {
+                targets: targets.into_iter().map(Into::into).collect(),
+            },
+            Command::Resume => EngineCommand::ResumeTimer,
+            Command::Extend { seconds } => EngineCommand::ExtendTimer { seconds },
+            Command::Assign {
+                command: Some(AssignCommand::Player { name }),
+            } => EngineCommand::Join { name, seat: None },
+            Command::Assign {
+                command: None | Some(AssignCommand::Role { role: None }),
+            } => EngineCommand::AssignRole,
+            Command::Assign {
+                command: Some(AssignCommand::Role { role: Some(role) }),
+            } => EngineCommand::DealRole { role },
+            other => return Err(other),
+        };
+        Ok(engine_cmd)
+    }
+}
@@ -1,18 +1,35 @@
 use ratatui::crossterm::event::KeyEvent;
 use std::fmt;
 
-use crate::engine::Event as EngineEvent;
+use crate::domain::Position;
+use crate::engine::{Event as EngineEvent, commands::Command as EngineCommand};
 
 #[derive(Debug, Clone)]
 pub enum Event {
     Key(KeyEvent),
     Engine(EngineEvent),
+    Reverted(EngineEvent),
     End,
+    Loaded(String),
+    Exported(String),
     TimerStarted(u64),
     TimerTick(u64),
     TimerEnded,
+    /// The clock of the engine's current turn ran out; carries the
+    /// generation of the countdown that sent it
+    TurnTimeUp(u64),
     Error(String),
     QuitRequested,
+    /// A night action sent from a seat's device, awaiting the host
+    SeatAction {
+        seat: Position,
+        command: EngineCommand,
+    },
+    /// The PIN a seat's device claims its seat with, shown to the host only
+    SeatPin {
+        seat: Position,
+        pin: u16,
+    },
 }
 
 impl fmt::Display for Event {
@@ -20,12 +37,32 @@ impl fmt::Display for Event {
         match self {
             Event::Key(key) => write!(f, "Key pressed: {key:?}"),
             Event::Engine(event) => write!(f, "{event}"),
+            Event::Reverted(event) => write!(f, "Reverted: {event}"),
             Event::TimerStarted(s) => write!(f, "Timer started: {s}s"),
             Event::TimerTick(s) => write!(f, "Timer: {s}s"),
             Event::TimerEnded => write!(f, "Timer ended"),
+            Event::TurnTimeUp(_) => write!(f, "Time is up"),
             Event::Error(e) => write!(f, "Error: {e}"),
             Event::QuitRequested => write!(f, "Quit requested"),
             Event::End => write!(f, "End game"),
+            Event::Loaded(path) => write!(f, "Game loaded from {path}"),
+            Event::Exported(path) => write!(f, "Game exported to {path}"),
+            Event::SeatAction { seat, command } => {
+                write!(f, "{seat} sent ")?;
+                match command {
+                    EngineCommand::Shoot { target } => write!(f, "shoot {target}")?,
+                    EngineCommand::Check { target } => write!(f, "check {target}")?,
+                    EngineCommand::Heal { target } => write!(f, "heal {target}")?,
+                    EngineCommand::Block { target } => write!(f, "block {target}")?,
+                    EngineCommand::Guess { targets } => {
+                        let targets: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+                        write!(f, "guess {}", targets.join(" "))?
+                    }
+                    other => write!(f, "{other:?}")?,
+                }
+                write!(f, ", `confirm` to apply")
+            }
+            Event::SeatPin { seat, pin } => write!(f, "PIN for {seat}: {pin}"),
         }
     }
 }
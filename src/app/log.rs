@@ -0,0 +1,103 @@
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::domain::{Activity, EngineState};
+use crate::engine::{Engine, Event as EngineEvent};
+
+/// One logged event, stamped with when and where in the game it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Wall-clock time, RFC 3339 with milliseconds.
+    pub at: String,
+    pub day: usize,
+    /// `None` while in the lobby.
+    pub activity: Option<Activity>,
+    pub record: Record,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Record {
+    Engine(EngineEvent),
+    Reverted(EngineEvent),
+    /// App event, as shown in the event list.
+    App(String),
+}
+
+/// Every event of the current game, appended to a JSON Lines file as it
+/// happens so a crash loses nothing.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    path: Option<PathBuf>,
+    entries: Vec<Entry>,
+}
+
+impl EventLog {
+    /// Log that is also written to `path`.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: Some(path.as_ref().to_path_buf()),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Replaces the entries kept for saving, e.g. with those of a loaded
+    /// game. The file keeps everything.
+    pub fn reset(&mut self, entries: Vec<Entry>) {
+        self.entries = entries;
+    }
+
+    pub fn record(&mut self, engine: &Engine, record: Record) -> anyhow::Result<()> {
+        let activity = match engine.state {
+            EngineState::Game(activity) => Some(activity),
+            EngineState::Lobby(_) => None,
+        };
+        let entry = Entry {
+            at: Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+            day: engine.day.current(),
+            activity,
+            record,
+        };
+
+        if let Some(path) = &self.path {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        }
+        self.entries.push(entry);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::rules::RuleSet;
+
+    #[test]
+    fn entries_are_stamped_and_appended_to_the_file() {
+        let path = std::env::temp_dir().join(format!("mafia-log-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut log = EventLog::new(&path);
+        let engine = Engine::new(RuleSet::default());
+
+        log.record(&engine, Record::Engine(EngineEvent::GameStarted))
+            .unwrap();
+        log.record(&engine, Record::App("Timer ended".to_string()))
+            .unwrap();
+        let lines = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(log.entries().len(), 2);
+        assert_eq!(log.entries()[0].activity, None);
+        let first: Entry = serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+        assert_eq!(first, log.entries()[0]);
+        assert!(chrono::DateTime::parse_from_rfc3339(&first.at).is_ok());
+    }
+}
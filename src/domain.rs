@@ -1,9 +1,11 @@
+pub mod ability;
 pub mod engine;
 pub mod lobby;
 pub mod phase;
 pub mod position;
 pub mod role;
 pub mod status;
+pub mod team;
 
 pub use engine::EngineState;
 pub use lobby::LobbyStatus;
@@ -13,3 +15,4 @@ pub use phase::{
 pub use position::Position;
 pub use role::Role;
 pub use status::Status;
+pub use team::Team;
@@ -0,0 +1,125 @@
+use super::{NightActivity, Role, Team};
+
+/// What a role does when it wakes up at night.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ability {
+    /// Shoots with the rest of the mafia; the kill needs every living,
+    /// unblocked mafioso on the same seat.
+    Shoot,
+    /// Learns which side a seat plays for.
+    Check,
+    /// Kills alone, whatever the mafia did.
+    Kill,
+    /// The seat survives every shot of the night.
+    Heal,
+    /// The seat sleeps through the rest of the night; its actions fail.
+    Block,
+}
+
+/// A turn a role takes every night after the first day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NightTurn {
+    pub activity: NightActivity,
+    pub ability: Ability,
+    /// Turns are played in ascending order.
+    pub order: u8,
+}
+
+/// Everything the engine needs to know about a role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoleSpec {
+    pub team: Team,
+    pub turns: &'static [NightTurn],
+    /// No more than one card of the role may be in the deck.
+    pub unique: bool,
+}
+
+const BLOCK: NightTurn = NightTurn {
+    activity: NightActivity::ProstituteBlock,
+    ability: Ability::Block,
+    order: 10,
+};
+const MAFIA_SHOOTING: NightTurn = NightTurn {
+    activity: NightActivity::MafiaShooting,
+    ability: Ability::Shoot,
+    order: 20,
+};
+const MANIAC_KILL: NightTurn = NightTurn {
+    activity: NightActivity::ManiacKill,
+    ability: Ability::Kill,
+    order: 30,
+};
+const HEAL: NightTurn = NightTurn {
+    activity: NightActivity::DoctorHeal,
+    ability: Ability::Heal,
+    order: 40,
+};
+const SHERIFF_CHECK: NightTurn = NightTurn {
+    activity: NightActivity::SheriffCheck,
+    ability: Ability::Check,
+    order: 50,
+};
+const DON_CHECK: NightTurn = NightTurn {
+    activity: NightActivity::DonCheck,
+    ability: Ability::Check,
+    order: 60,
+};
+
+impl Role {
+    /// The role registry; a new role is declared here.
+    pub fn spec(&self) -> RoleSpec {
+        let spec = |team, turns, unique| RoleSpec {
+            team,
+            turns,
+            unique,
+        };
+        match self {
+            Role::Citizen => spec(Team::Town, &[], false),
+            Role::Mafia => spec(Team::Mafia, &[MAFIA_SHOOTING], false),
+            Role::Don => spec(Team::Mafia, &[MAFIA_SHOOTING, DON_CHECK], true),
+            Role::Sheriff => spec(Team::Town, &[SHERIFF_CHECK], true),
+            Role::Doctor => spec(Team::Town, &[HEAL], true),
+            Role::Maniac => spec(Team::Maniac, &[MANIAC_KILL], true),
+            Role::Prostitute => spec(Team::Town, &[BLOCK], true),
+            Role::Jester => spec(Team::Jester, &[], true),
+        }
+    }
+
+    /// Whether the role wakes up for `activity`.
+    pub fn wakes_for(&self, activity: NightActivity) -> bool {
+        self.spec().turns.iter().any(|t| t.activity == activity)
+    }
+}
+
+/// Night turns of the roles in play, in the order they are played.
+pub fn night_sequence(roles: impl IntoIterator<Item = Role>) -> Vec<NightActivity> {
+    let mut turns: Vec<NightTurn> = roles
+        .into_iter()
+        .flat_map(|role| role.spec().turns.iter().copied())
+        .collect();
+    turns.sort_by_key(|t| t.order);
+    turns.dedup_by_key(|t| t.activity);
+    turns.into_iter().map(|t| t.activity).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn night_follows_the_roles_in_play() {
+        use NightActivity::*;
+
+        let classic = [Role::Don, Role::Mafia, Role::Sheriff, Role::Citizen];
+        assert_eq!(
+            night_sequence(classic),
+            vec![MafiaShooting, SheriffCheck, DonCheck]
+        );
+
+        let casual = [Role::Doctor, Role::Mafia, Role::Prostitute, Role::Maniac];
+        assert_eq!(
+            night_sequence(casual),
+            vec![ProstituteBlock, MafiaShooting, ManiacKill, DoctorHeal]
+        );
+    }
+}
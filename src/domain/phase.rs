@@ -1,7 +1,7 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Day {
     Night,
     Morning,
@@ -21,13 +21,16 @@ impl fmt::Display for Day {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NightActivity {
     RoleAssignment, // game setup zero night only
     SheriffReveal,
     DonReveal,
     MafiaBriefing,
+    ProstituteBlock,
     MafiaShooting,
+    ManiacKill,
+    DoctorHeal,
     SheriffCheck,
     DonCheck,
 }
@@ -40,14 +43,17 @@ impl fmt::Display for NightActivity {
             SheriffReveal => write!(f, "Sheriff Reveal"),
             DonReveal => write!(f, "Don Reveal"),
             MafiaBriefing => write!(f, "Mafia Briefing"),
+            ProstituteBlock => write!(f, "Prostitute Block"),
             MafiaShooting => write!(f, "Mafia Shooting"),
+            ManiacKill => write!(f, "Maniac Kill"),
+            DoctorHeal => write!(f, "Doctor Heal"),
             SheriffCheck => write!(f, "Sheriff Check"),
             DonCheck => write!(f, "Don Check"),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MorningActivity {
     DeathSpeech, // optional, single actor
     Guessing,    // optional, single actor, 3 guesses
@@ -63,7 +69,7 @@ impl fmt::Display for MorningActivity {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NoonActivity {
     Discussion,
 }
@@ -77,7 +83,7 @@ impl fmt::Display for NoonActivity {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EveningActivity {
     Voting,
     TieDiscussion,
@@ -99,7 +105,7 @@ impl fmt::Display for EveningActivity {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Activity {
     Night(NightActivity),
     Morning(MorningActivity),
@@ -130,7 +136,9 @@ impl Activity {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 pub struct DayIndex(pub usize);
 
 impl DayIndex {
@@ -1,9 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::snapshot::Snapshot;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct Position(u8);
 
 impl Position {
@@ -31,6 +31,8 @@ impl Position {
             8 => "8️⃣",
             9 => "9️⃣",
             10 => "🔟",
+            11 => "1️⃣1️⃣",
+            12 => "1️⃣2️⃣",
             _ => "?",
         }
     }
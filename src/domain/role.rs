@@ -1,14 +1,21 @@
 use clap::ValueEnum;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::str::FromStr;
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Copy, ValueEnum, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Copy, ValueEnum, Serialize, Deserialize)]
 pub enum Role {
     #[default]
     Citizen,
     Mafia,
     Don,
     Sheriff,
+    Doctor,
+    Maniac,
+    /// Also known as the Lover.
+    #[value(alias = "lover")]
+    #[serde(alias = "Lover")]
+    Prostitute,
+    Jester,
 }
 
 impl Display for Role {
@@ -25,6 +32,10 @@ impl From<Role> for &'static str {
             Role::Mafia => "Mafia",
             Role::Don => "Don",
             Role::Sheriff => "Sheriff",
+            Role::Doctor => "Doctor",
+            Role::Maniac => "Maniac",
+            Role::Prostitute => "Prostitute",
+            Role::Jester => "Jester",
         }
     }
 }
@@ -44,6 +55,10 @@ impl FromStr for Role {
             "mafia" => Ok(Role::Mafia),
             "don" => Ok(Role::Don),
             "sheriff" => Ok(Role::Sheriff),
+            "doctor" => Ok(Role::Doctor),
+            "maniac" => Ok(Role::Maniac),
+            "prostitute" | "lover" => Ok(Role::Prostitute),
+            "jester" => Ok(Role::Jester),
             other => Err(format!("Invalid role: {other}")),
         }
     }
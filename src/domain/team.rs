@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use super::Role;
+
+/// Side a role plays for; the game is won by a team, not by a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Team {
+    Town,
+    Mafia,
+    /// Plays for no one but themselves.
+    Maniac,
+    /// Wants to be voted out.
+    Jester,
+}
+
+impl Display for Team {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Team::Town => write!(f, "Town"),
+            Team::Mafia => write!(f, "Mafia"),
+            Team::Maniac => write!(f, "Maniac"),
+            Team::Jester => write!(f, "Jester"),
+        }
+    }
+}
+
+impl Role {
+    pub fn team(&self) -> Team {
+        self.spec().team
+    }
+}
@@ -1,24 +1,39 @@
 pub mod actor;
 pub mod commands;
 pub mod game;
+pub mod journal;
+pub mod result;
+pub mod rules;
+pub mod timer;
 pub mod turn;
 
+#[cfg(test)]
+mod scenario;
+
 use std::{collections::HashSet, fmt};
 
 use actor::Actor;
-use serde::Serialize;
+use journal::{Journal, Outcome};
+use result::{GameResult, Standing};
+use rules::{Overtime, RuleSet, SingleNominee};
+use serde::{Deserialize, Serialize};
+use timer::Timer;
 use turn::Turn;
 
-use self::{commands::Command, game::Game};
+use self::{
+    commands::{Command, CommandKind},
+    game::Game,
+};
 use crate::{
     domain::{
         Activity, DayIndex, EngineState, EveningActivity, LobbyStatus, MorningActivity,
-        NightActivity, NoonActivity, Position,
+        NightActivity, NoonActivity, Position, Role, Team,
     },
-    snapshot::{self, Snapshot},
+    snapshot::{self, Restore, Snapshot},
 };
-use anyhow::{Ok, Result, anyhow, bail};
+use anyhow::{Ok, Result, bail};
 use rand::prelude::*;
+use rand::rngs::StdRng;
 
 #[derive(Debug)]
 pub struct Engine {
@@ -27,6 +42,11 @@ pub struct Engine {
     pub last_discussion_started: Position,
     pub day: DayIndex,
     pub state: EngineState,
+    pub result: Option<GameResult>,
+    timer: Option<Timer>,
+    rng: StdRng,
+    journal: Journal,
+    undone: Vec<Command>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -43,13 +63,79 @@ pub enum Error {
 
     #[error(transparent)]
     Check(#[from] game::check::Error),
+
+    #[error(transparent)]
+    Timer(#[from] timer::Error),
+
+    #[error("Replay diverged from the journal at command {index}")]
+    ReplayDiverged { index: usize },
+
+    #[error("Game is over: {0}")]
+    GameOver(GameResult),
+
+    #[error("Nothing to undo")]
+    NothingToUndo,
+
+    #[error("Nothing to redo")]
+    NothingToRedo,
+
+    #[error("Only night actions and guesses can be sent from a seat")]
+    NotASeatAction,
+
+    #[error("This action is not taken in the current phase")]
+    ActionOutOfPhase,
+
+    #[error("It is not {0}'s turn")]
+    NotYourTurn(Position),
+
+    #[error("Engine is not in Game state")]
+    NotInGame,
+
+    #[error("Engine is not in the lobby, got {0:?}")]
+    NotInLobby(EngineState),
+
+    #[error("Expected the lobby to be {expected:?}, got {actual:?}")]
+    InvalidLobbyStatus {
+        expected: LobbyStatus,
+        actual: EngineState,
+    },
+
+    #[error("Expected the {expected} phase, got {actual:?}")]
+    InvalidPhase {
+        expected: Activity,
+        actual: EngineState,
+    },
+
+    #[error("Nobody is acting right now")]
+    NoActor,
+
+    #[error("No {0} at the table")]
+    RoleMissing(Role),
+
+    #[error("Player at {0} has no role")]
+    NoRole(Position),
+
+    #[error("No vote is open today")]
+    NoVoting,
+
+    #[error("Nobody has left the game today")]
+    NobodyOut,
+
+    #[error("Cannot {0} now")]
+    NotAllowed(&'static str),
+
+    #[error("Cannot {command} {seat} now")]
+    SeatNotAllowed {
+        command: &'static str,
+        seat: Position,
+    },
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Event {
     // lifecycle
     GameStarted,
-    GameEnded,
+    GameEnded { result: GameResult },
     PhaseAdvanced { from: Activity, to: Activity },
 
     // actor
@@ -57,6 +143,7 @@ pub enum Event {
 
     // domain passthrough
     Game(game::Event),
+    Timer(timer::Event),
 }
 
 impl fmt::Display for Event {
@@ -65,8 +152,8 @@ impl fmt::Display for Event {
             Event::GameStarted => {
                 write!(f, "Game has started")
             }
-            Event::GameEnded => {
-                write!(f, "Game has ended")
+            Event::GameEnded { result } => {
+                write!(f, "Game has ended: {result}")
             }
             Event::PhaseAdvanced { from, to } => {
                 write!(f, "Game phase advanced from  {from} to {to}")
@@ -75,6 +162,7 @@ impl fmt::Display for Event {
                 write!(f, "Actor advanced to {to}")
             }
             Event::Game(event) => write!(f, "{event}"),
+            Event::Timer(event) => write!(f, "{event}"),
         }
     }
 }
@@ -93,28 +181,171 @@ impl Snapshot for Engine {
             phase,
             day: self.day.current(),
             state: self.state,
+            result: self.result,
+            timer: self.timer.as_ref().map(Timer::snapshot),
+            last_discussion_started: self.last_discussion_started,
         }
     }
 }
 
+impl Restore for Engine {
+    type Input = snapshot::Engine;
+    type Error = Error;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        let actor = Actor::restore(&snapshot.actor).unwrap_or_else(|never| match never {});
+        let game = Game::restore(&snapshot.game)?;
+        let seed = rand::random();
+        Result::Ok(Engine {
+            game,
+            actor,
+            last_discussion_started: snapshot.last_discussion_started,
+            day: DayIndex::new(snapshot.day),
+            state: snapshot.state,
+            result: snapshot.result,
+            timer: snapshot
+                .timer
+                .as_ref()
+                .map(|t| Timer::restore(t).unwrap_or_else(|never| match never {})),
+            rng: StdRng::seed_from_u64(seed),
+            journal: Journal::new(seed, snapshot.game.rules.clone(), Some(snapshot.clone())),
+            undone: Vec::new(),
+        })
+    }
+}
+
 impl Engine {
-    pub fn new() -> Self {
+    pub fn new(rules: RuleSet) -> Self {
+        Self::with_seed(rules, rand::random())
+    }
+
+    /// Creates an engine whose seat and role draws are fully determined by `seed`.
+    pub fn with_seed(rules: RuleSet, seed: u64) -> Self {
         Engine {
-            game: Game::new(),
+            game: Game::new(rules.clone()),
             actor: Actor::new(Position::new(1)),
             last_discussion_started: Position::new(0),
             day: DayIndex::new(0),
             state: EngineState::Lobby(LobbyStatus::Waiting),
+            result: None,
+            timer: None,
+            rng: StdRng::seed_from_u64(seed),
+            journal: Journal::new(seed, rules, None),
+            undone: Vec::new(),
+        }
+    }
+
+    /// Rebuilds an engine by re-applying every journaled command that was
+    /// not undone, failing as soon as a command produces a different
+    /// outcome than recorded.
+    pub fn replay(journal: &Journal) -> Result<Engine, Error> {
+        let mut engine = match journal.base() {
+            Some(base) => Engine::restore(base)?,
+            None => Engine::with_seed(journal.rules().clone(), journal.seed()),
+        };
+        engine.rng = StdRng::seed_from_u64(journal.seed());
+        engine.journal = Journal::new(
+            journal.seed(),
+            journal.rules().clone(),
+            journal.base().cloned(),
+        );
+
+        for (index, entry) in journal.entries().iter().enumerate() {
+            if entry.reverted {
+                engine.journal.push(entry.clone());
+                continue;
+            }
+            let result = engine.apply(entry.command.clone());
+            if Outcome::from(&result) != entry.outcome {
+                return Err(Error::ReplayDiverged { index });
+            }
         }
+
+        Result::Ok(engine)
+    }
+
+    pub fn journal(&self) -> &Journal {
+        &self.journal
     }
 
     pub fn apply(&mut self, cmd: Command) -> Result<Vec<Event>> {
-        match cmd {
-            Command::Join { name } => self.join(&name),
+        let result = self.execute(cmd.clone());
+        self.journal.record(cmd, &result);
+        if result.is_ok() {
+            self.undone.clear();
+        }
+        result
+    }
+
+    /// Rolls back the last successfully applied command by marking it (and
+    /// any rejected commands after it) as reverted and replaying the rest
+    /// of the journal. Returns the events that command had produced.
+    pub fn undo(&mut self) -> Result<Vec<Event>> {
+        let (index, events) = self
+            .journal
+            .entries()
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, entry)| !entry.reverted)
+            .find_map(|(index, entry)| match &entry.outcome {
+                Outcome::Applied(events) => Some((index, events.clone())),
+                Outcome::Rejected(_) => None,
+            })
+            .ok_or(Error::NothingToUndo)?;
+        let command = self.journal.entries()[index].command.clone();
+
+        let mut journal = self.journal.clone();
+        journal.revert_from(index);
+
+        let mut engine = Engine::replay(&journal)?;
+        engine.undone = std::mem::take(&mut self.undone);
+        engine.undone.push(command);
+        *self = engine;
+
+        Ok(events)
+    }
+
+    /// Re-applies the most recently undone command.
+    pub fn redo(&mut self) -> Result<Vec<Event>> {
+        let command = self.undone.pop().ok_or(Error::NothingToRedo)?;
+
+        let mut undone = std::mem::take(&mut self.undone);
+        let result = self.apply(command.clone());
+        // A failed redo keeps the command for another try
+        if result.is_err() {
+            undone.push(command);
+        }
+        self.undone = undone;
+
+        result
+    }
+
+    fn execute(&mut self, cmd: Command) -> Result<Vec<Event>> {
+        if let Some(result) = self.result {
+            bail!(Error::GameOver(result));
+        }
+        self.ensure_allowed(&cmd)?;
+
+        let turn = (self.state, self.actor.current());
+        let mut events = match cmd {
+            Command::Join { name, seat } => self.join(&name, seat),
             Command::Leave { name } => self.leave(&name),
+            Command::ResizeTable { seats } => self.resize_table(seats),
+            Command::SwapSeats { from, to } => self.swap_seats(from, to),
             Command::Start => self.start(),
-            Command::AssignRole => self.assign_role(self.actor.current().unwrap()),
-            Command::RevokeRole => self.revoke_role(self.actor.current().unwrap()),
+            Command::AssignRole => {
+                let position = self.current_actor()?;
+                self.assign_role(position)
+            }
+            Command::RevokeRole => {
+                let position = self.current_actor()?;
+                self.revoke_role(position)
+            }
+            Command::DealRole { role } => {
+                let position = self.current_actor()?;
+                self.deal_role(position, role)
+            }
             Command::Advance => self.advance(),
             Command::Warn { target } => self.warn(target),
             Command::Pardon { target } => self.pardon(target),
@@ -122,19 +353,105 @@ impl Engine {
             Command::Vote { targets } => self.vote(targets),
             Command::Shoot { target } => self.shoot(target),
             Command::Check { target } => self.check(target),
+            Command::Heal { target } => self.heal(target),
+            Command::Block { target } => self.block(target),
             Command::Guess { targets } => self.guess(targets.as_slice()),
+            Command::PauseTimer { remaining } => self.pause_timer(remaining),
+            Command::ResumeTimer => self.resume_timer(),
+            Command::ExtendTimer { seconds } => self.extend_timer(seconds),
+            Command::ExpireTimer => self.expire_timer(),
+        }?;
+
+        // Every new turn gets a fresh clock
+        if (self.state, self.actor.current()) != turn {
+            events.extend(self.restart_timer());
+        }
+
+        // Any command may take a player out of the game
+        events.extend(self.end());
+        if self.result.is_some() {
+            self.timer = None;
+        }
+        Ok(events)
+    }
+
+    pub fn timer(&self) -> Option<&Timer> {
+        self.timer.as_ref()
+    }
+
+    // ------------------------------
+    // Timer
+    // ------------------------------
+
+    /// Starts the clock of the current turn, or stops it if the turn is not
+    /// timed.
+    fn restart_timer(&mut self) -> Option<Event> {
+        self.timer = None;
+        let EngineState::Game(activity) = self.state else {
+            return None;
+        };
+        let speaker = self.actor.current()?;
+        let silenced = self
+            .game
+            .player_by_position(speaker)
+            .is_some_and(|p| p.is_silenced());
+        let seconds = self.game.rules().timers.limit(activity, silenced)?;
+
+        let (timer, event) = Timer::start(speaker, seconds, timer::is_speech(activity));
+        self.timer = Some(timer);
+        Some(Event::Timer(event))
+    }
+
+    fn running_timer(&mut self) -> Result<&mut Timer, timer::Error> {
+        self.timer.as_mut().ok_or(timer::Error::NotRunning)
+    }
+
+    fn pause_timer(&mut self, remaining: u64) -> Result<Vec<Event>> {
+        let event = self.running_timer()?.pause(remaining)?;
+        Ok(vec![Event::Timer(event)])
+    }
+
+    fn resume_timer(&mut self) -> Result<Vec<Event>> {
+        let event = self.running_timer()?.resume()?;
+        Ok(vec![Event::Timer(event)])
+    }
+
+    fn extend_timer(&mut self, seconds: u64) -> Result<Vec<Event>> {
+        let event = self.running_timer()?.extend(seconds);
+        Ok(vec![Event::Timer(event)])
+    }
+
+    /// Stops the clock and, if a speech ran over, applies the overtime rule.
+    fn expire_timer(&mut self) -> Result<Vec<Event>> {
+        let timer = self.running_timer()?.clone();
+        let mut events = vec![Event::Timer(timer.expire()?)];
+        self.timer = None;
+
+        if timer.is_speech() {
+            match self.game.rules().timers.overtime {
+                Overtime::Nothing => {}
+                Overtime::Warn => events.extend(self.warn(timer.speaker())?),
+                Overtime::Advance => events.extend(self.advance()?),
+            }
         }
+        Ok(events)
     }
 
     // ------------------------------
     // Join / Leave
     // ------------------------------
-    fn join(&mut self, name: &str) -> Result<Vec<Event>, anyhow::Error> {
+    fn join(&mut self, name: &str, seat: Option<Position>) -> Result<Vec<Event>, anyhow::Error> {
         self.ensure_lobby_waiting()?;
+        if let Some(seat) = seat {
+            self.ensure_free_seat(seat)?;
+        }
 
         let mut events = Vec::new();
         events.extend(self.game.add_player(name)?);
-        self.assign_position(name)?;
+        match seat {
+            Some(seat) => self.seat_player(name, seat)?,
+            None => self.assign_position(name)?,
+        };
 
         // As for now position assignment is happning simultaneously with joining,
         // this is good enough but if seprate those processes later, this logic should be updated
@@ -172,8 +489,12 @@ impl Engine {
         let position = *self
             .game
             .available_positions()
-            .choose(&mut rand::rng())
+            .choose(&mut self.rng)
             .ok_or_else(|| anyhow::anyhow!("No available positions"))?;
+        self.seat_player(name, position)
+    }
+
+    fn seat_player(&mut self, name: &str, position: Position) -> Result<Vec<Event>> {
         self.game.take_position(position)?;
 
         let player = self
@@ -189,6 +510,20 @@ impl Engine {
             .collect())
     }
 
+    fn swap_seats(&mut self, from: Position, to: Position) -> Result<Vec<Event>> {
+        self.ensure_lobby()?;
+        if self.game.player_by_position(to).is_none() {
+            self.ensure_free_seat(to)?;
+        }
+
+        Ok(self
+            .game
+            .swap_seats(from, to)?
+            .into_iter()
+            .map(Event::Game)
+            .collect())
+    }
+
     fn revoke_position(&mut self, name: &str) -> Result<Vec<Event>, anyhow::Error> {
         self.ensure_lobby()?;
 
@@ -212,6 +547,37 @@ impl Engine {
             .collect())
     }
 
+    fn resize_table(&mut self, seats: u8) -> Result<Vec<Event>> {
+        self.ensure_lobby()?;
+
+        let rules = self.game.rules().resized(seats)?;
+        let mut events: Vec<Event> = self
+            .game
+            .set_rules(rules)?
+            .into_iter()
+            .map(Event::Game)
+            .collect();
+
+        // Players who lost their seat draw a new one on the smaller table
+        self.state = EngineState::Lobby(LobbyStatus::Waiting);
+        let unseated: Vec<String> = self
+            .game
+            .players()
+            .iter()
+            .filter(|p| p.position().is_none())
+            .map(|p| p.name().to_string())
+            .collect();
+        for name in unseated {
+            events.extend(self.assign_position(&name)?);
+        }
+
+        if self.game.available_positions().is_empty() {
+            self.state = EngineState::Lobby(LobbyStatus::Ready);
+        }
+
+        Ok(events)
+    }
+
     fn start(&mut self) -> Result<Vec<Event>> {
         self.ensure_lobby_ready()?;
 
@@ -224,64 +590,99 @@ impl Engine {
         Ok(vec![Event::GameStarted])
     }
 
-    fn end(&mut self) -> Result<Vec<Event>> {
-        let mut events = Vec::new();
-        let mafia = self.game.players().iter().fold(0, |count, p| {
-            if p.is_mafia() && p.is_alive() {
-                count + 1
-            } else {
-                count
-            }
-        });
-        let citizens = self.game.players().iter().fold(0, |count, p| {
-            if !p.is_mafia() && p.is_alive() {
-                count + 1
-            } else {
-                count
+    /// Records the result once a team has won. Idempotent.
+    fn end(&mut self) -> Vec<Event> {
+        if self.result.is_some() {
+            return Vec::new();
+        }
+        match self.evaluate_result() {
+            Some(result) => {
+                self.result = Some(result);
+                let mut events: Vec<Event> = self
+                    .game
+                    .evaluate_guesses()
+                    .into_iter()
+                    .map(Event::Game)
+                    .collect();
+                events.push(Event::GameEnded { result });
+                events
             }
-        });
+            None => Vec::new(),
+        }
+    }
 
-        if mafia == 0 || mafia >= citizens {
-            events.push(Event::GameEnded);
-        } else {
-            return Ok(events);
+    /// Decides the game from the living players, if it is decided.
+    fn evaluate_result(&self) -> Option<GameResult> {
+        // Nothing can be decided before every player knows their role
+        if !matches!(self.state, EngineState::Game(_))
+            || self.game.players().iter().any(|p| p.role().is_none())
+        {
+            return None;
         }
-        Ok(events)
+
+        let mut standing = Standing::default();
+        for player in self.game.players() {
+            if let Some(team) = player.team() {
+                standing.record(team, player.is_alive(), player.is_eliminated());
+            }
+        }
+
+        let (winner, reason) = Team::PRIORITY
+            .into_iter()
+            .find_map(|team| team.wins(&standing).map(|reason| (team, reason)))?;
+
+        Some(GameResult {
+            winner,
+            reason,
+            day: self.day,
+        })
     }
 
     fn assign_role(&mut self, position: Position) -> Result<Vec<Event>> {
         self.ensure_role_assignment()?;
 
-        if self
-            .game
-            .player_by_position(position)
-            .unwrap()
-            .role()
-            .is_some()
-        {
+        if self.player(position)?.role().is_some() {
             return Ok(vec![]); // already has role
         }
         // Pick random role
         let role = *self
             .game
             .available_roles()
-            .choose(&mut rand::rng())
+            .choose(&mut self.rng)
             .ok_or_else(|| anyhow::anyhow!("No available roles"))?;
         self.game.take_role(role)?;
 
-        let player = self.game.player_by_position_mut(position).unwrap();
-        player.assign_role(role)?;
+        self.player_mut(position)?.assign_role(role)?;
+        Ok(vec![])
+    }
+
+    fn deal_role(&mut self, position: Position, role: Role) -> Result<Vec<Event>> {
+        self.ensure_role_assignment()?;
+
+        // Advancing draws a random card; a pre-drawn one replaces it
+        let drawn = self.player(position)?.role();
+        if drawn != Some(role) && !self.game.available_roles().contains(&role) {
+            bail!(game::Error::RoleUnavailable(role));
+        }
+        if drawn.is_some() {
+            self.revoke_role(position)?;
+        }
+
+        self.game.take_role(role)?;
+        self.player_mut(position)?.assign_role(role)?;
         Ok(vec![])
     }
 
     fn revoke_role(&mut self, position: Position) -> Result<Vec<Event>> {
         self.ensure_role_assignment()?;
 
-        let player = self.game.player_by_position(position).unwrap();
-        self.game.return_role(player.role().unwrap());
+        let role = self
+            .player(position)?
+            .role()
+            .ok_or(Error::NoRole(position))?;
+        self.game.return_role(role);
 
-        let player = self.game.player_by_position_mut(position).unwrap();
-        player.revoke_role()?;
+        self.player_mut(position)?.revoke_role()?;
         Ok(vec![])
     }
 
@@ -292,39 +693,29 @@ impl Engine {
     fn warn(&mut self, target: Position) -> Result<Vec<Event>, anyhow::Error> {
         let mut events = Vec::new();
         self.ensure_alive(target)?;
-        events.extend(self.end()?);
-
+        let penalties = self.game.rules().warnings;
         events.extend(
-            self.game
-                .player_by_position_mut(target)
-                .ok_or(Error::Game(game::Error::PlayerByPositionNotFound(target)))?
-                .warn()?
+            self.player_mut(target)?
+                .warn(penalties)?
                 .into_iter()
                 .map(game::Event::Player)
                 .map(Event::Game)
                 .collect::<Vec<Event>>(),
         ); // returns Vec<player::Event>
 
-        if self.game.player_by_position(target).unwrap().is_removed() {
-            self.set_phase(Activity::Night(NightActivity::MafiaShooting))?;
-            self.actor.reset(
-                self.game
-                    .players()
-                    .iter()
-                    .find(|p| p.is_mafia() && p.is_alive())
-                    .expect("At least one mafia must be alive")
-                    .position()
-                    .expect("Must have assigned position"),
-            );
-            self.day.advance();
+        // A removal that decides the game leaves the night to `end`
+        if self.player(target)?.is_removed() && self.evaluate_result().is_none() {
+            let dusk = self.dusk();
+            self.set_phase(Activity::Night(dusk))?;
+            self.begin_night(dusk)?;
         }
         Ok(events)
     }
 
     fn pardon(&mut self, target: Position) -> Result<Vec<Event>> {
         self.ensure_alive(target)?;
-        let player = self.game.player_by_position_mut(target).unwrap();
-        let events = player.pardon()?;
+        let penalties = self.game.rules().warnings;
+        let events = self.player_mut(target)?.pardon(penalties)?;
         Ok(events
             .into_iter()
             .map(game::Event::Player)
@@ -332,19 +723,168 @@ impl Engine {
             .collect())
     }
 
+    /// Commands accepted right now, with the seats and roles they may name.
+    /// Each phase allows its own set; `apply` rejects everything else.
+    pub fn allowed_commands(&self) -> Vec<CommandKind> {
+        use Activity::*;
+        use CommandKind as Kind;
+
+        let phase = match self.state {
+            EngineState::Lobby(LobbyStatus::Waiting) => {
+                return vec![Kind::Join, Kind::Leave, Kind::ResizeTable, Kind::SwapSeats];
+            }
+            EngineState::Lobby(LobbyStatus::Ready) => {
+                return vec![Kind::Leave, Kind::ResizeTable, Kind::SwapSeats, Kind::Start];
+            }
+            EngineState::Game(_) if self.result.is_some() => return Vec::new(),
+            EngineState::Game(phase) => phase,
+        };
+        let alive: Vec<Position> = self
+            .game
+            .players()
+            .iter()
+            .filter(|p| p.is_alive())
+            .filter_map(|p| p.position())
+            .collect();
+
+        let mut allowed = vec![Kind::Advance];
+        // The action of the turn, once somebody is acting
+        if let Some(actor) = self.actor.current() {
+            let targets = alive.clone();
+            match phase {
+                Night(NightActivity::RoleAssignment) => {
+                    let drawn = self.game.player_by_position(actor).and_then(|p| p.role());
+                    allowed.push(match drawn {
+                        Some(_) => Kind::RevokeRole,
+                        None => Kind::AssignRole,
+                    });
+                    let mut roles: Vec<Role> = drawn.into_iter().collect();
+                    for role in self.game.available_roles() {
+                        if !roles.contains(role) {
+                            roles.push(*role);
+                        }
+                    }
+                    allowed.push(Kind::DealRole { roles });
+                }
+                Night(NightActivity::ProstituteBlock) => allowed.push(Kind::Block { targets }),
+                Night(NightActivity::MafiaShooting | NightActivity::ManiacKill) => {
+                    allowed.push(Kind::Shoot { targets })
+                }
+                Night(NightActivity::DoctorHeal) => allowed.push(Kind::Heal { targets }),
+                Night(NightActivity::SheriffCheck | NightActivity::DonCheck) => {
+                    allowed.push(Kind::Check { targets })
+                }
+                Morning(MorningActivity::Guessing) => allowed.push(Kind::Guess { targets }),
+                Noon(NoonActivity::Discussion) if alive.contains(&actor) => {
+                    allowed.push(Kind::Nominate { targets })
+                }
+                Evening(EveningActivity::Voting | EveningActivity::TieVoting) => {
+                    allowed.push(Kind::Vote { voters: targets })
+                }
+                _ => {}
+            }
+        }
+        if phase == Evening(EveningActivity::FinalVoting) {
+            allowed.push(Kind::Vote {
+                voters: alive.clone(),
+            });
+        }
+
+        allowed.push(Kind::Warn {
+            targets: alive.clone(),
+        });
+        allowed.push(Kind::Pardon { targets: alive });
+        if let Some(timer) = &self.timer {
+            if timer.is_paused() {
+                allowed.push(Kind::ResumeTimer);
+            } else {
+                allowed.extend([Kind::PauseTimer, Kind::ExpireTimer]);
+            }
+            allowed.push(Kind::ExtendTimer);
+        }
+        allowed
+    }
+
+    /// Rejects a command `allowed_commands` does not offer, or one naming a
+    /// seat or role it does not allow.
+    fn ensure_allowed(&self, command: &Command) -> Result<(), Error> {
+        let allowed = self.allowed_commands();
+        let kind = allowed
+            .iter()
+            .find(|kind| kind.is_kind_of(command))
+            .ok_or(Error::NotAllowed(command.name()))?;
+
+        if let Some(seats) = kind.seats()
+            && let Some(seat) = command.seats().iter().find(|s| !seats.contains(s))
+        {
+            return Err(Error::SeatNotAllowed {
+                command: command.name(),
+                seat: *seat,
+            });
+        }
+        if let (CommandKind::DealRole { roles }, Command::DealRole { role }) = (kind, command)
+            && !roles.contains(role)
+        {
+            return Err(game::Error::RoleUnavailable(*role).into());
+        }
+        Result::Ok(())
+    }
+
+    /// Checks that `seat` may send `command` from its own device: only the
+    /// night action of the current activity, and only on its own turn.
+    ///
+    /// Nothing is applied; the host confirms the action afterwards.
+    pub fn check_seat_action(&self, seat: Position, command: &Command) -> Result<(), Error> {
+        use Activity::{Morning, Night};
+
+        let expected = match command {
+            Command::Shoot { .. } => matches!(
+                self.state,
+                EngineState::Game(Night(
+                    NightActivity::MafiaShooting | NightActivity::ManiacKill
+                ))
+            ),
+            Command::Check { .. } => matches!(
+                self.state,
+                EngineState::Game(Night(NightActivity::SheriffCheck | NightActivity::DonCheck))
+            ),
+            Command::Heal { .. } => matches!(
+                self.state,
+                EngineState::Game(Night(NightActivity::DoctorHeal))
+            ),
+            Command::Block { .. } => matches!(
+                self.state,
+                EngineState::Game(Night(NightActivity::ProstituteBlock))
+            ),
+            Command::Guess { .. } => {
+                matches!(
+                    self.state,
+                    EngineState::Game(Morning(MorningActivity::Guessing))
+                )
+            }
+            _ => return Err(Error::NotASeatAction),
+        };
+        if !expected {
+            return Err(Error::ActionOutOfPhase);
+        }
+        if self.actor.current() != Some(seat) {
+            return Err(Error::NotYourTurn(seat));
+        }
+
+        Result::Ok(())
+    }
+
     fn shoot(&mut self, target: Position) -> Result<Vec<Event>, anyhow::Error> {
         let mut events = Vec::new();
         self.ensure_alive(target)?;
-        if let Some(actor) = self.actor.current() {
-            events.extend(
-                self.game
-                    .record_shoot(self.day, actor, target)?
-                    .into_iter()
-                    .map(Event::Game),
-            );
-        } else {
-            return Err(anyhow::anyhow!("No active shooter"));
-        }
+        let actor = self.current_actor()?;
+        let recorded = match self.phase()? {
+            Activity::Night(NightActivity::ManiacKill) => {
+                self.game.record_maniac_shot(self.day, target)?
+            }
+            _ => self.game.record_shoot(self.day, actor, target)?,
+        };
+        events.extend(recorded.into_iter().map(Event::Game));
 
         Ok(events)
     }
@@ -357,25 +897,40 @@ impl Engine {
             Activity::Night(NightActivity::DonCheck) => {
                 self.game.record_don_check(self.day, target)?;
             }
-            _ => bail!("Not in investigation phase"),
+            _ => bail!(Error::ActionOutOfPhase),
         };
 
         Ok(vec![])
     }
 
-    fn guess(&mut self, geusses: &[Position]) -> Result<Vec<Event>> {
-        for guess in geusses {
-            self.game.record_guess(*guess)?;
-        }
-        Ok(vec![])
+    fn heal(&mut self, target: Position) -> Result<Vec<Event>> {
+        self.ensure_phase(Activity::Night(NightActivity::DoctorHeal))?;
+        self.current_actor()?;
+        self.ensure_alive(target)?;
+
+        let events = self.game.record_heal(self.day, target)?;
+        Ok(events.into_iter().map(Event::Game).collect())
+    }
+
+    fn block(&mut self, target: Position) -> Result<Vec<Event>> {
+        self.ensure_phase(Activity::Night(NightActivity::ProstituteBlock))?;
+        self.current_actor()?;
+        self.ensure_alive(target)?;
+
+        let events = self.game.record_block(self.day, target)?;
+        Ok(events.into_iter().map(Event::Game).collect())
+    }
+
+    fn guess(&mut self, targets: &[Position]) -> Result<Vec<Event>> {
+        let by = self.current_actor()?;
+
+        let events = self.game.record_guess(by, targets)?;
+        Ok(events.into_iter().map(Event::Game).collect())
     }
 
     fn nominate(&mut self, target: Position) -> Result<Vec<Event>> {
         self.ensure_discussion()?;
-        let by = self
-            .actor
-            .current()
-            .ok_or_else(|| anyhow::anyhow!("No active speaker"))?;
+        let by = self.current_actor()?;
         self.ensure_alive(by)?;
         self.ensure_alive(target)?;
 
@@ -386,43 +941,32 @@ impl Engine {
     fn vote(&mut self, voters: Vec<Position>) -> Result<Vec<Event>> {
         let phase = self.phase()?;
 
-        match phase {
+        let events = match phase {
             // ---------- FIRST VOTING ----------
             Activity::Evening(EveningActivity::Voting) => {
-                let nominee = self
-                    .actor
-                    .current()
-                    .ok_or_else(|| anyhow!("No active nominee. Call AdvanceActor first"))?;
+                let nominee = self.current_actor()?;
 
                 self.game
-                    .add_vote_batch(self.day, game::Pool::Main, nominee, &voters)?;
+                    .add_vote_batch(self.day, game::Pool::Main, nominee, &voters)?
             }
 
             // ---------- TIE VOTING ----------
             Activity::Evening(EveningActivity::TieVoting) => {
-                let nominee = self
-                    .actor
-                    .current()
-                    .ok_or_else(|| anyhow!("No active nominee. Call AdvanceActor first"))?;
+                let nominee = self.current_actor()?;
 
                 self.game
-                    .add_vote_batch(self.day, game::Pool::Tie, nominee, &voters)?;
+                    .add_vote_batch(self.day, game::Pool::Tie, nominee, &voters)?
             }
 
             // ---------- FINAL YES / NO ----------
             Activity::Evening(EveningActivity::FinalVoting) => {
-                self.game.add_vote_batch(
-                    self.day,
-                    game::Pool::Final,
-                    Position::new(0), /* placholder TODO */
-                    &voters,
-                )?;
+                self.game.add_final_votes(self.day, &voters)?
             }
 
-            _ => bail!("Not in a voting phase"),
-        }
+            _ => bail!(Error::ActionOutOfPhase),
+        };
 
-        Ok(vec![])
+        Ok(events.into_iter().map(Event::Game).collect())
     }
 
     fn advance(&mut self) -> Result<Vec<Event>> {
@@ -433,7 +977,7 @@ impl Engine {
         use NoonActivity::*;
 
         let current = self.phase()?;
-        let next = self.next(current);
+        let next = self.next(current)?;
 
         let event = match current {
             // -------- Night --------
@@ -448,13 +992,7 @@ impl Engine {
 
                 // 2. If phase completed → transition
                 if self.actor.is_completed() {
-                    self.actor.reset(
-                        self.game
-                            .sheriff()
-                            .expect("Sheriff should exist")
-                            .position()
-                            .expect("Sheriff must have assigned position"),
-                    );
+                    self.actor.reset(self.seat_of(Role::Sheriff)?);
 
                     self.set_phase(next)?;
                     vec![Event::PhaseAdvanced {
@@ -463,18 +1001,9 @@ impl Engine {
                     }]
                 } else {
                     // 3. Assign role to the NEW current actor if needed
-                    let position = self
-                        .actor
-                        .current()
-                        .expect("Actor must exist during role assignment");
+                    let position = self.current_actor()?;
 
-                    if self
-                        .game
-                        .player_by_position(position)
-                        .expect("Player must exist")
-                        .role()
-                        .is_none()
-                    {
+                    if self.player(position)?.role().is_none() {
                         self.assign_role(position)?;
                     }
 
@@ -490,13 +1019,7 @@ impl Engine {
                 });
 
                 if self.actor.is_completed() {
-                    self.actor.reset(
-                        self.game
-                            .don()
-                            .expect("Don should exist")
-                            .position()
-                            .expect("Don must have assigned position"),
-                    );
+                    self.actor.reset(self.seat_of(Role::Don)?);
                     self.set_phase(next)?;
                     vec![Event::PhaseAdvanced {
                         from: current,
@@ -504,22 +1027,13 @@ impl Engine {
                     }]
                 } else {
                     vec![Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at sheriff reveal"),
+                        to: self.current_actor()?,
                     }]
                 }
             }
             Night(DonReveal) => {
-                let don = self
-                    .game
-                    .players()
-                    .iter()
-                    .find(|p| p.is_don() && p.is_alive())
-                    .expect("Don must exist")
-                    .position()
-                    .expect("Don must have assigned position");
+                // A Don removed by warnings still opens the briefing
+                let don = self.seat_of(Role::Don)?;
                 self.game.next_actor(&mut self.actor, |pos| {
                     self.game
                         .player_by_position(pos)
@@ -539,10 +1053,7 @@ impl Engine {
                     }]
                 } else {
                     vec![Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at don reveal"),
+                        to: self.current_actor()?,
                     }]
                 }
             }
@@ -565,175 +1076,73 @@ impl Engine {
                     }]
                 } else {
                     vec![Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at don reveal"),
+                        to: self.current_actor()?,
                     }]
                 }
             }
 
-            Night(MafiaShooting) => {
-                let mut events = Vec::new();
-                self.game.next_actor(&mut self.actor, |pos| {
-                    self.game
-                        .player_by_position(pos)
-                        .map(|p| p.is_mafia() && p.is_alive())
-                        .unwrap_or(false)
-                });
+            Night(
+                turn @ (ProstituteBlock | MafiaShooting | ManiacKill | DoctorHeal | SheriffCheck
+                | DonCheck),
+            ) => {
+                let awake = self.awake(self.day, turn);
+                self.game
+                    .next_actor(&mut self.actor, |pos| awake.contains(&pos));
 
                 if self.actor.is_completed() {
-                    let mafia_shots = self
-                        .game
-                        .players()
-                        .iter()
-                        .filter_map(|p| {
-                            if p.is_mafia() && p.is_alive() {
-                                p.shot(self.day)
+                    let mut events = Vec::new();
+                    let next = match self.night_turn_after(self.day, Some(turn)) {
+                        Some(turn) => {
+                            self.actor.reset(self.first_awake(turn)?);
+                            Night(turn)
+                        }
+                        None => {
+                            // Kills only land once every role has acted
+                            let target = self.shooting_target();
+                            events.extend(
+                                self.game
+                                    .resolve_night(self.day, target)?
+                                    .into_iter()
+                                    .map(Event::Game),
+                            );
+                            let next = self.next(current)?;
+                            if next == Noon(Discussion) {
+                                let first_speaker_of_discussion =
+                                    self.compute_first_speaker_of_day();
+                                self.actor.reset(first_speaker_of_discussion);
+                                self.last_discussion_started = first_speaker_of_discussion;
                             } else {
-                                None
+                                self.actor.reset(self.first_killed()?);
                             }
-                        })
-                        .collect::<Vec<Position>>();
-
-                    if mafia_shots.windows(2).all(|w| w[0] == w[1]) {
-                        let target = mafia_shots[0];
-                        let player = self
-                            .game
-                            .player_by_position_mut(target)
-                            .expect("Target player must exist and have assigned position");
-
-                        events.extend(
-                            player
-                                .mark_dead()?
-                                .into_iter()
-                                .map(game::Event::Player)
-                                .map(Event::Game)
-                                .collect::<Vec<Event>>(),
-                        );
-                        events.extend(
-                            self.game
-                                .record_mafia_kill(self.day, target)?
-                                .into_iter()
-                                .map(Event::Game)
-                                .collect::<Vec<Event>>(),
-                        );
-                        events.extend(self.end()?);
-                    }
-                    self.actor.reset(
-                        self.game
-                            .sheriff()
-                            .expect("Sheriff should exist")
-                            .position()
-                            .expect("Sheriff must have assigned position"),
-                    );
+                            next
+                        }
+                    };
                     self.set_phase(next)?;
                     events.push(Event::PhaseAdvanced {
                         from: current,
                         to: next,
                     });
                     events
-                } else {
-                    events.push(Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at mafia shooting"),
-                    });
-                    events
-                }
-            }
-            Night(SheriffCheck) => {
-                self.game.next_actor(&mut self.actor, |pos| {
-                    self.game
-                        .player_by_position(pos)
-                        .map(|p| p.is_sheriff())
-                        .unwrap_or(false)
-                });
-
-                if self.actor.is_completed() {
-                    self.actor.reset(
-                        self.game
-                            .don()
-                            .expect("Don should exist")
-                            .position()
-                            .expect("Don must have assigned position"),
-                    );
-                    self.set_phase(next)?;
-                    vec![Event::PhaseAdvanced {
-                        from: current,
-                        to: next,
-                    }]
-                } else {
-                    vec![Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at sheriff reveal"),
-                    }]
-                }
-            }
-            Night(DonCheck) => {
-                self.game.next_actor(&mut self.actor, |pos| {
-                    self.game
-                        .player_by_position(pos)
-                        .map(|p| p.is_don())
-                        .unwrap_or(false)
-                });
-
-                if self.actor.is_completed() {
-                    if next == Noon(Discussion) {
-                        self.set_phase(next)?;
-                        let first_speaker_of_discussion = self.compute_first_speaker_of_day();
-                        self.actor.reset(first_speaker_of_discussion);
-                        self.last_discussion_started = first_speaker_of_discussion;
-                        vec![Event::PhaseAdvanced {
-                            from: current,
-                            to: next,
-                        }]
-                    } else {
-                        self.actor.reset(
-                            *self
-                                .game
-                                .get_kill(self.day)
-                                .expect("Killed player must exist"),
-                        );
-                        self.set_phase(next)?;
-                        vec![Event::PhaseAdvanced {
-                            from: current,
-                            to: next,
-                        }]
-                    }
                 } else {
                     vec![Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at don reveal"),
+                        to: self.current_actor()?,
                     }]
                 }
             }
 
             // -------- Morning --------
             Morning(Guessing) => {
-                let killed_p = self
-                    .game
-                    .get_kill(self.day)
-                    .expect("Killed player must exist");
+                let killed = *self.game.get_kill(self.day).ok_or(Error::NobodyOut)?;
                 self.game.next_actor(&mut self.actor, |pos| {
-                    self.game
-                        .player_by_position(pos)
-                        .map(|p| p.is_dead() && *killed_p == p.position().unwrap())
-                        .unwrap_or(false)
+                    pos == killed
+                        && self
+                            .game
+                            .player_by_position(pos)
+                            .is_some_and(|p| p.is_dead())
                 });
 
                 if self.actor.is_completed() {
-                    self.actor.reset(
-                        *self
-                            .game
-                            .get_kill(self.day)
-                            .expect("Killed player must exist"),
-                    );
+                    self.actor.reset(self.first_killed()?);
                     self.set_phase(next)?;
                     vec![Event::PhaseAdvanced {
                         from: current,
@@ -741,22 +1150,16 @@ impl Engine {
                     }]
                 } else {
                     vec![Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at don reveal"),
+                        to: self.current_actor()?,
                     }]
                 }
             }
             Morning(DeathSpeech) => {
-                let killed_p = self
-                    .game
-                    .get_kill(self.day)
-                    .expect("Killed player must exist");
+                let killed = self.game.killed(self.day);
                 self.game.next_actor(&mut self.actor, |pos| {
                     self.game
                         .player_by_position(pos)
-                        .map(|p| p.is_dead() && *killed_p == p.position().unwrap())
+                        .map(|p| p.is_dead() && killed.contains(&pos))
                         .unwrap_or(false)
                 });
 
@@ -772,10 +1175,7 @@ impl Engine {
                     }]
                 } else {
                     vec![Event::ActorAdvanced {
-                        to: self
-                            .actor
-                            .current()
-                            .expect("Actor must exist at don reveal"),
+                        to: self.current_actor()?,
                     }]
                 }
             }
@@ -786,10 +1186,7 @@ impl Engine {
                 let previous_actor = self.actor.current();
 
                 if let Some(previous_actor) = previous_actor {
-                    let player = self
-                        .game
-                        .player_by_position_mut(previous_actor)
-                        .expect("Player at given position must exist");
+                    let player = self.player_mut(previous_actor)?;
 
                     if player.is_silenced() {
                         player.restore_speaking();
@@ -808,27 +1205,13 @@ impl Engine {
                 }
 
                 if self.actor.is_completed() {
-                    if next == Night(MafiaShooting) {
-                        self.actor.reset(
-                            self.game
-                                .players()
-                                .iter()
-                                .find(|p| p.is_mafia() && p.is_alive())
-                                .expect("At least one mafia must be alive")
-                                .position()
-                                .expect("Must have assigned position"),
-                        );
-                        self.day.advance();
+                    if let Night(dusk) = next {
+                        self.begin_night(dusk)?;
                     }
 
                     if next == Evening(Voting) {
-                        let nominees = self
-                            .game
-                            .voting()
-                            .get(&self.day)
-                            .expect("Voting must exist")
-                            .get_nominees();
-                        self.actor.reset(nominees[0]);
+                        let first = self.voting()?.get_nominees().first().copied();
+                        self.actor.reset(first.ok_or(Error::NoVoting)?);
                     }
                     self.set_phase(next)?;
                     events.push(Event::PhaseAdvanced {
@@ -844,12 +1227,7 @@ impl Engine {
             // -------- Evening --------
             Evening(Voting) => {
                 let mut events = Vec::new();
-                let voting = self
-                    .game
-                    .voting()
-                    .get(&self.day)
-                    .expect("Voting must exist")
-                    .clone();
+                let voting = self.voting()?.clone();
                 let voters = self
                     .game
                     .players()
@@ -861,24 +1239,15 @@ impl Engine {
                 let previous_actor = self.actor.current();
 
                 if let Some(previous_actor) = previous_actor {
-                    self.game
-                        .voting_mut()
-                        .get_mut(&self.day)
-                        .expect("Voting must exist")
-                        .finalize_nominee(previous_actor);
+                    self.voting_mut()?.finalize_nominee(previous_actor);
                     events.push(Event::Game(game::Event::Voting(
                         game::voting::Event::Finalized {
                             nominee: previous_actor,
                         },
                     )));
 
-                    if previous_actor == nominees[nominees.len() - 1]
-                        && !remaining_voters.is_empty()
-                    {
-                        self.game
-                            .voting_mut()
-                            .get_mut(&self.day)
-                            .expect("Voting must exist")
+                    if nominees.last() == Some(&previous_actor) && !remaining_voters.is_empty() {
+                        self.voting_mut()?
                             .cast_implicit_votes_for_nominee()
                             .into_iter()
                             .for_each(|e| {
@@ -889,17 +1258,28 @@ impl Engine {
 
                 // borrow voting again so would have up to date voting state
                 // in case it was mutated above
-                let voting = self
-                    .game
-                    .voting()
-                    .get(&self.day)
-                    .expect("Voting must exist");
+                let voting = self.game.voting().get(&self.day).ok_or(Error::NoVoting)?;
                 voting.next_actor(&mut self.actor, |_| true);
                 if let Some(pos) = self.actor.current() {
                     events.push(Event::ActorAdvanced { to: pos });
                 }
 
+                // Nobody is left to vote for after the last nominee, so the
+                // remaining votes go to them straight away
+                if self.actor.current() == nominees.last().copied()
+                    && !voting.remaining_voters().is_empty()
+                {
+                    self.voting_mut()?
+                        .cast_implicit_votes_for_nominee()
+                        .into_iter()
+                        .for_each(|e| {
+                            events.push(Event::Game(game::Event::Voting(e)));
+                        });
+                }
+                let voting = self.voting()?;
+
                 if self.actor.is_completed() {
+                    voting.ensure_counted()?;
                     let winners = voting.winners();
                     let tie_nominees = voting
                         .get_nominees()
@@ -910,26 +1290,17 @@ impl Engine {
 
                     if winners.len() == 1 {
                         self.game.record_eliminated(self.day, &winners)?;
-                        self.game
-                            .player_by_position_mut(winners[0])
-                            .unwrap()
-                            .mark_eliminated()?;
+                        self.player_mut(winners[0])?.mark_eliminated()?;
                         self.actor.reset(winners[0]);
-                        self.set_phase(self.next(current))?;
-                        events.extend(self.end()?);
+                        self.set_phase(self.next(current)?)?;
                     } else {
                         self.game.tie_voting_mut().insert(
                             self.day,
                             game::voting::Voting::from_nominees(&tie_nominees, voters),
                         );
-                        let tie_nominees = self
-                            .game
-                            .tie_voting()
-                            .get(&self.day)
-                            .expect("Tie Voting must exist")
-                            .get_nominees();
-                        self.actor.reset(tie_nominees[0]);
-                        self.set_phase(self.next(current))?;
+                        let first = self.tie_voting()?.get_nominees().first().copied();
+                        self.actor.reset(first.ok_or(Error::NoVoting)?);
+                        self.set_phase(self.next(current)?)?;
                     }
                     events.push(Event::PhaseAdvanced {
                         from: current,
@@ -946,7 +1317,7 @@ impl Engine {
                     .game
                     .tie_voting()
                     .get(&self.day)
-                    .expect("Voting must exist");
+                    .ok_or(Error::NoVoting)?;
 
                 voting.next_actor(&mut self.actor, |_| true);
 
@@ -955,7 +1326,8 @@ impl Engine {
                 }
 
                 if self.actor.is_completed() {
-                    self.actor.reset(voting.get_nominees()[0]);
+                    let first = voting.get_nominees().first().copied();
+                    self.actor.reset(first.ok_or(Error::NoVoting)?);
                     self.set_phase(next)?;
                     events.push(Event::PhaseAdvanced {
                         from: current,
@@ -968,35 +1340,21 @@ impl Engine {
             }
             Evening(TieVoting) => {
                 let mut events = Vec::new();
-                let voting = self
-                    .game
-                    .tie_voting()
-                    .get(&self.day)
-                    .expect("Tie Voting must exist")
-                    .clone();
+                let voting = self.tie_voting()?.clone();
                 let nominees = voting.get_nominees();
                 let remaining_voters = voting.remaining_voters();
                 let previous_actor = self.actor.current();
 
                 if let Some(previous_actor) = previous_actor {
-                    self.game
-                        .tie_voting_mut()
-                        .get_mut(&self.day)
-                        .expect("Voting must exist")
-                        .finalize_nominee(previous_actor);
+                    self.tie_voting_mut()?.finalize_nominee(previous_actor);
                     events.push(Event::Game(game::Event::Voting(
                         game::voting::Event::Finalized {
                             nominee: previous_actor,
                         },
                     )));
 
-                    if previous_actor == nominees[nominees.len() - 1]
-                        && !remaining_voters.is_empty()
-                    {
-                        self.game
-                            .tie_voting_mut()
-                            .get_mut(&self.day)
-                            .expect("Voting must exist")
+                    if nominees.last() == Some(&previous_actor) && !remaining_voters.is_empty() {
+                        self.tie_voting_mut()?
                             .cast_implicit_votes_for_nominee()
                             .into_iter()
                             .for_each(|e| {
@@ -1011,26 +1369,38 @@ impl Engine {
                     .game
                     .tie_voting()
                     .get(&self.day)
-                    .expect("Voting must exist");
+                    .ok_or(Error::NoVoting)?;
                 voting.next_actor(&mut self.actor, |_| true);
 
                 if let Some(pos) = self.actor.current() {
                     events.push(Event::ActorAdvanced { to: pos });
                 }
 
+                // Nobody is left to vote for after the last nominee, so the
+                // remaining votes go to them straight away
+                if self.actor.current() == nominees.last().copied()
+                    && !voting.remaining_voters().is_empty()
+                {
+                    self.tie_voting_mut()?
+                        .cast_implicit_votes_for_nominee()
+                        .into_iter()
+                        .for_each(|e| {
+                            events.push(Event::Game(game::Event::Voting(e)));
+                        });
+                }
+                let voting = self.tie_voting()?;
+
                 if self.actor.is_completed() {
+                    voting.ensure_counted()?;
                     let winners = voting.winners();
 
                     if winners.len() == 1 {
                         self.game.record_eliminated(self.day, &winners)?;
-                        self.game
-                            .player_by_position_mut(winners[0])
-                            .unwrap()
-                            .mark_eliminated()?;
+                        self.player_mut(winners[0])?.mark_eliminated()?;
                         self.actor.reset(winners[0]);
                         self.set_phase(next)?;
-                        events.extend(self.end()?);
                     } else {
+                        self.game.open_final_vote(self.day, winners);
                         self.set_phase(next)?;
                     }
 
@@ -1045,43 +1415,17 @@ impl Engine {
             }
             Evening(FinalVoting) => {
                 let mut events = Vec::new();
-                let yes_count = self
-                    .game
-                    .final_voting()
-                    .get(&self.day)
-                    .map(|v| v.len())
-                    .unwrap_or(0);
-                let alive_count = self.game.alive_players();
 
-                let nominees = self
-                    .game
-                    .tie_voting()
-                    .get(&self.day)
-                    .map(|v| v.get_nominees().to_vec())
-                    .unwrap_or_default();
-
-                if yes_count > alive_count / 2 {
-                    self.game.record_eliminated(self.day, &nominees)?;
-                    for nominee in &nominees {
-                        self.game
-                            .player_by_position_mut(*nominee)
-                            .unwrap()
-                            .mark_eliminated()?;
-                    }
-                    self.actor.reset(nominees[0]);
-                    events.extend(self.end()?);
-                } else {
-                    self.actor.reset(
-                        self.game
-                            .players()
-                            .iter()
-                            .find(|p| p.is_mafia() && p.is_alive())
-                            .expect("At least one mafia must be alive")
-                            .position()
-                            .expect("Must have assigned position"),
-                    );
-
-                    self.day.advance();
+                if self.game.final_vote_passes(self.day) {
+                    events.extend(self.game.lift_all(self.day)?.into_iter().map(Event::Game));
+                    let lifted = self
+                        .game
+                        .get_eliminated(self.day)
+                        .and_then(|e| e.first().copied())
+                        .ok_or(Error::NobodyOut)?;
+                    self.actor.reset(lifted);
+                } else if let Night(dusk) = next {
+                    self.begin_night(dusk)?;
                 }
 
                 self.set_phase(next)?;
@@ -1093,10 +1437,7 @@ impl Engine {
             }
             Evening(FinalSpeech) => {
                 let mut events = Vec::new();
-                let eliminated = self
-                    .game
-                    .get_eliminated(self.day)
-                    .expect("there shoul be eliminted players, at least one");
+                let eliminated = self.game.get_eliminated(self.day).ok_or(Error::NobodyOut)?;
                 self.game.next_actor(&mut self.actor, |pos| {
                     self.game
                         .player_by_position(pos)
@@ -1109,16 +1450,9 @@ impl Engine {
 
                 if self.actor.is_completed() {
                     self.set_phase(next)?;
-                    self.day.advance();
-                    self.actor.reset(
-                        self.game
-                            .players()
-                            .iter()
-                            .find(|p| p.is_mafia() && p.is_alive())
-                            .expect("At least one mafia must be alive")
-                            .position()
-                            .expect("Must have assigned position"),
-                    );
+                    if let Night(dusk) = next {
+                        self.begin_night(dusk)?;
+                    }
                     events.push(Event::PhaseAdvanced {
                         from: current,
                         to: next,
@@ -1133,14 +1467,83 @@ impl Engine {
         Ok(event)
     }
 
-    fn next(&self, phase: Activity) -> Activity {
+    /// The seat every living mafioso shot tonight. Differing shots, or any
+    /// mafioso not shooting at all, make the shooting a miss. A blocked
+    /// mafioso's shot does not count.
+    fn shooting_target(&self) -> Option<Position> {
+        let mut shots = self
+            .game
+            .players()
+            .iter()
+            .filter(|p| p.is_mafia() && p.is_alive())
+            .filter(|p| {
+                !p.position()
+                    .is_some_and(|pos| self.game.is_blocked(self.day, pos))
+            })
+            .map(|p| p.shot(self.day));
+
+        let target = shots.next()??;
+        shots.all(|shot| shot == Some(target)).then_some(target)
+    }
+
+    /// Seats that wake up for `turn` in the night of `day`: living players
+    /// of a role acting in it, unless they are blocked.
+    fn awake(&self, day: DayIndex, turn: NightActivity) -> Vec<Position> {
+        self.game
+            .players()
+            .iter()
+            .filter(|p| p.is_alive() && p.role().is_some_and(|r| r.wakes_for(turn)))
+            .filter_map(|p| p.position())
+            .filter(|pos| !self.game.is_blocked(day, *pos))
+            .collect()
+    }
+
+    fn first_awake(&self, turn: NightActivity) -> Result<Position, Error> {
+        self.awake(self.day, turn)
+            .into_iter()
+            .min()
+            .ok_or(Error::NoActor)
+    }
+
+    /// The turn played after `after` in the night of `day`; turns nobody
+    /// wakes up for are skipped.
+    fn night_turn_after(
+        &self,
+        day: DayIndex,
+        after: Option<NightActivity>,
+    ) -> Option<NightActivity> {
+        let sequence = self.game.rules().night_sequence();
+        let skip = after
+            .and_then(|after| sequence.iter().position(|t| *t == after))
+            .map_or(0, |i| i + 1);
+        sequence
+            .into_iter()
+            .skip(skip)
+            .find(|turn| !self.awake(day, *turn).is_empty())
+    }
+
+    /// First turn of the coming night.
+    fn dusk(&self) -> NightActivity {
+        self.night_turn_after(self.day.next(), None)
+            .unwrap_or(NightActivity::MafiaShooting)
+    }
+
+    /// Moves on to the night of the next day and wakes the first seat of
+    /// its first turn.
+    fn begin_night(&mut self, dusk: NightActivity) -> Result<()> {
+        self.day.advance();
+        self.actor.reset(self.first_awake(dusk)?);
+        Ok(())
+    }
+
+    fn next(&self, phase: Activity) -> Result<Activity, Error> {
         use Activity::*;
         use EveningActivity::*;
         use MorningActivity::*;
         use NightActivity::*;
         use NoonActivity::*;
 
-        match phase {
+        let next = match phase {
             // -------- Night --------
             Night(RoleAssignment) => {
                 if self.game.players().iter().all(|p| p.role().is_some()) {
@@ -1152,17 +1555,24 @@ impl Engine {
             Night(SheriffReveal) => Night(DonReveal),
             Night(DonReveal) => Night(MafiaBriefing),
             Night(MafiaBriefing) => Noon(Discussion),
-            Night(MafiaShooting) => Night(SheriffCheck),
-            Night(SheriffCheck) => Night(DonCheck),
-            Night(DonCheck) => {
-                if self.day.is_second() && self.game.get_kill(self.day).is_some() {
-                    Morning(Guessing)
-                } else if self.game.get_kill(self.day).is_some() {
-                    Morning(DeathSpeech)
-                } else {
-                    Noon(Discussion)
+            Night(
+                turn @ (ProstituteBlock | MafiaShooting | ManiacKill | DoctorHeal | SheriffCheck
+                | DonCheck),
+            ) => match self.night_turn_after(self.day, Some(turn)) {
+                Some(turn) => Night(turn),
+                None => {
+                    if self.game.rules().best_guess.enabled
+                        && self.day.is_second()
+                        && self.game.get_kill(self.day).is_some()
+                    {
+                        Morning(Guessing)
+                    } else if !self.game.killed(self.day).is_empty() {
+                        Morning(DeathSpeech)
+                    } else {
+                        Noon(Discussion)
+                    }
                 }
-            }
+            },
 
             // -------- Morning --------
             Morning(Guessing) => Morning(DeathSpeech),
@@ -1180,23 +1590,22 @@ impl Engine {
                 let voting = self.game.voting().get(&self.day).unwrap_or(&voting);
 
                 if voting.has_nominees() {
-                    if self.day.is_first() && voting.nominee_count() == 1 {
-                        Night(MafiaShooting)
+                    if self.day.is_first()
+                        && voting.nominee_count() == 1
+                        && self.game.rules().first_day_single_nominee == SingleNominee::Skip
+                    {
+                        Night(self.dusk())
                     } else {
                         Evening(Voting)
                     }
                 } else {
-                    Night(MafiaShooting)
+                    Night(self.dusk())
                 }
             }
 
             // -------- Evening --------
             Evening(Voting) => {
-                let voting = self
-                    .game
-                    .voting()
-                    .get(&self.day)
-                    .expect("Voting must exist at Voting");
+                let voting = self.voting()?;
                 if voting.is_finalized() && voting.winners().len() == 1 {
                     Evening(FinalSpeech)
                 } else {
@@ -1205,11 +1614,7 @@ impl Engine {
             }
             Evening(TieDiscussion) => Evening(TieVoting),
             Evening(TieVoting) => {
-                let voting = self
-                    .game
-                    .tie_voting()
-                    .get(&self.day)
-                    .expect("Voting must exist at Voting");
+                let voting = self.tie_voting()?;
                 if voting.is_finalized() && voting.winners().len() == 1 {
                     Evening(FinalSpeech)
                 } else {
@@ -1217,21 +1622,15 @@ impl Engine {
                 }
             }
             Evening(FinalVoting) => {
-                let yes_count = self
-                    .game
-                    .final_voting()
-                    .get(&self.day)
-                    .map(|v| v.len())
-                    .unwrap_or(0);
-                let alive_count = self.game.alive_players();
-                if yes_count > alive_count / 2 {
+                if self.game.final_vote_passes(self.day) {
                     Evening(FinalSpeech)
                 } else {
-                    Night(MafiaShooting)
+                    Night(self.dusk())
                 }
             }
-            Evening(FinalSpeech) => Night(MafiaShooting),
-        }
+            Evening(FinalSpeech) => Night(self.dusk()),
+        };
+        Result::Ok(next)
     }
 
     fn compute_first_speaker_of_day(&self) -> Position {
@@ -1244,7 +1643,7 @@ impl Engine {
 
         loop {
             // advance once
-            current = if current.value() == Game::PLAYER_COUNT {
+            current = if current.value() == self.game.rules().player_count {
                 Position::new(1)
             } else {
                 Position::new(current.value() + 1)
@@ -1261,7 +1660,7 @@ impl Engine {
     fn phase(&self) -> Result<Activity> {
         match self.state {
             EngineState::Game(phase) => Ok(phase),
-            _ => bail!("Engine is not in Game state"),
+            _ => bail!(Error::NotInGame),
         }
     }
 
@@ -1271,56 +1670,937 @@ impl Engine {
                 *p = phase;
                 Ok(())
             }
-            _ => bail!("Engine is not in Game state"),
+            _ => bail!(Error::NotInGame),
         }
     }
 
+    // ------------------------------
+    // Lookups
+    // ------------------------------
+    fn current_actor(&self) -> Result<Position, Error> {
+        self.actor.current().ok_or(Error::NoActor)
+    }
+
+    fn player(&self, position: Position) -> Result<&game::player::Player, Error> {
+        self.game
+            .player_by_position(position)
+            .ok_or(Error::Game(game::Error::PlayerByPositionNotFound(position)))
+    }
+
+    fn player_mut(&mut self, position: Position) -> Result<&mut game::player::Player, Error> {
+        self.game
+            .player_by_position_mut(position)
+            .ok_or(Error::Game(game::Error::PlayerByPositionNotFound(position)))
+    }
+
+    /// Seat of the player holding `role`, whether still in the game or not.
+    fn seat_of(&self, role: Role) -> Result<Position, Error> {
+        self.game
+            .players()
+            .iter()
+            .find(|p| p.role() == Some(role))
+            .and_then(|p| p.position())
+            .ok_or(Error::RoleMissing(role))
+    }
+
+    /// First of the players killed tonight.
+    fn first_killed(&self) -> Result<Position, Error> {
+        self.game
+            .killed(self.day)
+            .first()
+            .copied()
+            .ok_or(Error::NobodyOut)
+    }
+
+    fn voting(&self) -> Result<&game::voting::Voting, Error> {
+        self.game.voting().get(&self.day).ok_or(Error::NoVoting)
+    }
+
+    fn voting_mut(&mut self) -> Result<&mut game::voting::Voting, Error> {
+        self.game
+            .voting_mut()
+            .get_mut(&self.day)
+            .ok_or(Error::NoVoting)
+    }
+
+    fn tie_voting(&self) -> Result<&game::voting::Voting, Error> {
+        self.game.tie_voting().get(&self.day).ok_or(Error::NoVoting)
+    }
+
+    fn tie_voting_mut(&mut self) -> Result<&mut game::voting::Voting, Error> {
+        self.game
+            .tie_voting_mut()
+            .get_mut(&self.day)
+            .ok_or(Error::NoVoting)
+    }
+
     // ------------------------------
     // Guards
     // ------------------------------
-    pub fn ensure_lobby(&self) -> Result<()> {
-        match &self.state {
-            EngineState::Lobby(_) => Ok(()),
-            other => bail!("Engine is not in Lobby state, got {other:?}",),
+    pub fn ensure_lobby(&self) -> Result<(), Error> {
+        match self.state {
+            EngineState::Lobby(_) => Result::Ok(()),
+            other => Err(Error::NotInLobby(other)),
         }
     }
 
-    pub fn ensure_lobby_waiting(&self) -> Result<()> {
-        match &self.state {
-            EngineState::Lobby(LobbyStatus::Waiting) => Ok(()),
-            other => bail!("Engine is not in Lobby Waiting state, got {other:?}"),
-        }
+    pub fn ensure_lobby_waiting(&self) -> Result<(), Error> {
+        self.ensure_lobby_status(LobbyStatus::Waiting)
     }
 
-    pub fn ensure_lobby_ready(&self) -> Result<()> {
-        match &self.state {
-            EngineState::Lobby(LobbyStatus::Ready) => Ok(()),
-            other => bail!("Engine is not in Lobby Ready state, got {other:?}"),
+    pub fn ensure_free_seat(&self, seat: Position) -> Result<()> {
+        if self.game.available_positions().contains(&seat) {
+            Ok(())
+        } else {
+            bail!(game::Error::SeatTaken(seat))
         }
     }
 
-    pub fn ensure_role_assignment(&self) -> Result<()> {
-        match &self.phase()? {
-            Activity::Night(NightActivity::RoleAssignment) => Ok(()),
-            other => bail!("Engine is not in Role Assignment phase, got {other:?}"),
+    pub fn ensure_lobby_ready(&self) -> Result<(), Error> {
+        self.ensure_lobby_status(LobbyStatus::Ready)
+    }
+
+    fn ensure_lobby_status(&self, expected: LobbyStatus) -> Result<(), Error> {
+        if self.state != EngineState::Lobby(expected) {
+            return Err(Error::InvalidLobbyStatus {
+                expected,
+                actual: self.state,
+            });
         }
+        Result::Ok(())
     }
 
-    pub fn ensure_discussion(&self) -> Result<()> {
-        match &self.phase()? {
-            Activity::Noon(NoonActivity::Discussion) => Ok(()),
-            other => bail!("Engine is not in Discussion phase, got {other:?}"),
+    pub fn ensure_phase(&self, expected: Activity) -> Result<(), Error> {
+        if self.state != EngineState::Game(expected) {
+            return Err(Error::InvalidPhase {
+                expected,
+                actual: self.state,
+            });
         }
+        Result::Ok(())
+    }
+
+    pub fn ensure_role_assignment(&self) -> Result<(), Error> {
+        self.ensure_phase(Activity::Night(NightActivity::RoleAssignment))
+    }
+
+    pub fn ensure_discussion(&self) -> Result<(), Error> {
+        self.ensure_phase(Activity::Noon(NoonActivity::Discussion))
     }
 
     fn ensure_alive(&self, position: Position) -> Result<()> {
-        let player = self
-            .game
-            .player_by_position(position)
-            .ok_or_else(|| anyhow::anyhow!("Player not found"))?;
+        let player = self.player(position)?;
         if !player.is_alive() {
             bail!("Player {position:?} is not alive");
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use result::WinReason;
+
+    fn started_engine() -> Engine {
+        started_engine_with(RuleSet::default())
+    }
+
+    fn started_engine_with(rules: RuleSet) -> Engine {
+        let mut engine = Engine::new(rules);
+        for name in ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"] {
+            engine
+                .apply(Command::Join {
+                    name: name.to_string(),
+                    seat: None,
+                })
+                .unwrap();
+        }
+        engine.apply(Command::Start).unwrap();
+        engine
+    }
+
+    fn dealt_engine() -> Engine {
+        let mut engine = started_engine();
+        while engine.state == EngineState::Game(Activity::Night(NightActivity::RoleAssignment)) {
+            engine.apply(Command::Advance).unwrap();
+        }
+        engine
+    }
+
+    fn kill_where(engine: &mut Engine, team: Team, count: usize) {
+        for player in engine
+            .game
+            .players_mut()
+            .iter_mut()
+            .filter(|p| p.is_alive() && p.team() == Some(team))
+            .take(count)
+        {
+            player.mark_dead().unwrap();
+        }
+    }
+
+    #[test]
+    fn dealing_roles_does_not_decide_the_game() {
+        let engine = dealt_engine();
+
+        assert!(engine.game.players().iter().all(|p| p.role().is_some()));
+        assert_eq!(engine.result, None);
+    }
+
+    #[test]
+    fn town_wins_once_all_mafia_are_out() {
+        let mut engine = dealt_engine();
+        kill_where(&mut engine, Team::Mafia, 3);
+
+        let events = engine.end();
+        let result = engine.result.expect("game should be decided");
+
+        assert_eq!(result.winner, Team::Town);
+        assert_eq!(result.reason, WinReason::MafiaEliminated);
+        assert_eq!(events, vec![Event::GameEnded { result }]);
+        assert!(engine.end().is_empty());
+        assert!(engine.apply(Command::Advance).is_err());
+    }
+
+    #[test]
+    fn mafia_wins_at_parity() {
+        let mut engine = dealt_engine();
+        kill_where(&mut engine, Team::Town, 3);
+        assert!(engine.end().is_empty());
+
+        kill_where(&mut engine, Team::Town, 1);
+        engine.end();
+        let result = engine.result.expect("game should be decided");
+
+        assert_eq!(result.winner, Team::Mafia);
+        assert_eq!(result.reason, WinReason::MafiaParity);
+        assert_eq!(
+            engine.snapshot().result.map(|r| r.winner),
+            Some(Team::Mafia)
+        );
+    }
+
+    #[test]
+    fn seats_may_only_send_their_own_night_action() {
+        let mut engine = dealt_engine();
+        let shoot = Command::Shoot {
+            target: Position::new(1),
+        };
+        let mafioso = engine
+            .game
+            .players()
+            .iter()
+            .find(|p| p.is_mafia())
+            .and_then(|p| p.position())
+            .unwrap();
+        let other = Position::new(mafioso.value() % 10 + 1);
+
+        assert!(matches!(
+            engine.check_seat_action(mafioso, &shoot),
+            Err(Error::ActionOutOfPhase)
+        ));
+
+        engine.state = EngineState::Game(Activity::Night(NightActivity::MafiaShooting));
+        engine.actor.set_current(Some(mafioso));
+        assert!(engine.check_seat_action(mafioso, &shoot).is_ok());
+        assert!(matches!(
+            engine.check_seat_action(other, &shoot),
+            Err(Error::NotYourTurn(_))
+        ));
+        assert!(matches!(
+            engine.check_seat_action(mafioso, &Command::Advance),
+            Err(Error::NotASeatAction)
+        ));
+    }
+
+    /// Advances through the rest of the night, sending `action` for every
+    /// seat as it wakes up.
+    fn play_night(
+        engine: &mut Engine,
+        mut action: impl FnMut(NightActivity) -> Option<Command>,
+    ) -> Vec<Event> {
+        let mut events = Vec::new();
+        while let EngineState::Game(Activity::Night(turn)) = engine.state {
+            events.extend(engine.apply(Command::Advance).unwrap());
+            if engine.state == EngineState::Game(Activity::Night(turn))
+                && let Some(command) = action(turn)
+            {
+                events.extend(engine.apply(command).unwrap());
+            }
+        }
+        events
+    }
+
+    fn shooting_night(targets: &[Position]) -> Vec<Event> {
+        let mut engine = dealt_engine();
+        engine.state = EngineState::Game(Activity::Night(NightActivity::MafiaShooting));
+        let first_mafioso = engine
+            .game
+            .players()
+            .iter()
+            .find(|p| p.is_mafia())
+            .and_then(|p| p.position())
+            .unwrap();
+        engine.actor.reset(first_mafioso);
+
+        let mut targets = targets.iter();
+        play_night(&mut engine, |turn| {
+            (turn == NightActivity::MafiaShooting)
+                .then(|| targets.next())
+                .flatten()
+                .map(|&target| Command::Shoot { target })
+        })
+    }
+
+    fn town_seats(engine: &Engine) -> Vec<Position> {
+        engine
+            .game
+            .players()
+            .iter()
+            .filter(|p| p.team() == Some(Team::Town))
+            .filter_map(|p| p.position())
+            .collect()
+    }
+
+    #[test]
+    fn mafia_kill_needs_every_living_mafioso_on_the_same_seat() {
+        let town = town_seats(&dealt_engine());
+
+        let agreed = shooting_night(&[town[0]; 3]);
+        assert!(agreed.contains(&Event::Game(game::Event::MafiaKill { position: town[0] })));
+
+        let split = shooting_night(&[town[0], town[0], town[1]]);
+        assert!(split.contains(&Event::Game(game::Event::MafiaMissed)));
+
+        let one_passed = shooting_night(&[town[0], town[0]]);
+        assert!(one_passed.contains(&Event::Game(game::Event::MafiaMissed)));
+    }
+
+    #[test]
+    fn casual_roles_block_heal_and_kill_at_night() {
+        let mut rules = RuleSet::default();
+        for role in [Role::Doctor, Role::Maniac, Role::Prostitute] {
+            rules.roles.push(rules::RoleCount { role, count: 1 });
+        }
+        let mut engine = started_engine_with(rules);
+        while engine.state == EngineState::Game(Activity::Night(NightActivity::RoleAssignment)) {
+            engine.apply(Command::Advance).unwrap();
+        }
+        let seat = |engine: &Engine, role| {
+            engine
+                .game
+                .players()
+                .iter()
+                .find(|p| p.role() == Some(role))
+                .and_then(|p| p.position())
+                .unwrap()
+        };
+        let (don, prostitute) = (seat(&engine, Role::Don), seat(&engine, Role::Prostitute));
+        let town = town_seats(&engine);
+        let (healed, victim) = (town[0], town[1]);
+
+        engine.state = EngineState::Game(Activity::Night(NightActivity::ProstituteBlock));
+        engine.actor.reset(prostitute);
+        let mut played = Vec::new();
+        let events = play_night(&mut engine, |turn| {
+            played.push(turn);
+            match turn {
+                NightActivity::ProstituteBlock => Some(Command::Block { target: don }),
+                NightActivity::MafiaShooting => Some(Command::Shoot { target: healed }),
+                NightActivity::ManiacKill => Some(Command::Shoot { target: victim }),
+                NightActivity::DoctorHeal => Some(Command::Heal { target: healed }),
+                _ => None,
+            }
+        });
+
+        // The blocked Don neither shoots nor checks
+        assert!(!played.contains(&NightActivity::DonCheck));
+        assert_eq!(
+            played
+                .iter()
+                .filter(|t| **t == NightActivity::MafiaShooting)
+                .count(),
+            2
+        );
+        assert!(events.contains(&Event::Game(game::Event::Night(
+            game::night::Event::Saved { position: healed }
+        ))));
+        assert!(engine.game.player_by_position(healed).unwrap().is_alive());
+        assert!(engine.game.player_by_position(victim).unwrap().is_dead());
+        assert_eq!(engine.game.killed(engine.day), vec![victim]);
+        assert_eq!(
+            engine.state,
+            EngineState::Game(Activity::Morning(MorningActivity::DeathSpeech))
+        );
+        assert_eq!(engine.actor.start(), victim);
+    }
+
+    #[test]
+    fn best_guess_is_validated_and_scored_when_the_game_ends() {
+        let mut engine = dealt_engine();
+        let town = town_seats(&engine);
+        let mafia: Vec<Position> = engine
+            .game
+            .players()
+            .iter()
+            .filter(|p| p.is_mafia())
+            .filter_map(|p| p.position())
+            .collect();
+        let victim = town[0];
+        engine
+            .game
+            .player_by_position_mut(victim)
+            .unwrap()
+            .mark_dead()
+            .unwrap();
+
+        let rejected = [
+            vec![mafia[0], mafia[0]],
+            vec![mafia[0], mafia[1], mafia[2], town[1]],
+            vec![victim],
+        ];
+        for targets in rejected {
+            assert!(engine.game.record_guess(victim, &targets).is_err());
+        }
+        engine
+            .game
+            .record_guess(victim, &[mafia[0], mafia[1], town[1]])
+            .unwrap();
+        assert!(matches!(
+            engine.game.record_guess(victim, &[mafia[2]]),
+            Err(game::Error::AlreadyGuessed(_))
+        ));
+
+        kill_where(&mut engine, Team::Mafia, 3);
+        let events = engine.end();
+
+        assert_eq!(
+            events[0],
+            Event::Game(game::Event::GuessEvaluated {
+                by: victim,
+                hits: 2
+            })
+        );
+        assert_eq!(engine.snapshot().game.guesses[&victim].len(), 3);
+    }
+
+    #[test]
+    fn final_vote_lifts_all_candidates_on_a_majority() {
+        let mut engine = dealt_engine();
+        let town = town_seats(&engine);
+        let day = engine.day;
+        let candidates = vec![town[0], town[1]];
+        engine.game.open_final_vote(day, candidates.clone());
+        engine
+            .game
+            .player_by_position_mut(town[2])
+            .unwrap()
+            .mark_removed()
+            .unwrap();
+
+        assert!(engine.game.add_final_votes(day, &[town[2]]).is_err());
+        assert!(
+            engine
+                .game
+                .add_final_votes(day, &[town[3], town[3]])
+                .is_err()
+        );
+
+        // Nine players remain, so five yes votes are needed
+        let voters: Vec<Position> = (1..=10)
+            .map(Position::new)
+            .filter(|p| *p != town[2])
+            .collect();
+        engine.game.add_final_votes(day, &voters[..4]).unwrap();
+        assert!(!engine.game.final_vote_passes(day));
+        engine.game.add_final_votes(day, &voters[4..5]).unwrap();
+        assert!(engine.game.final_vote_passes(day));
+
+        let events = engine.game.lift_all(day).unwrap();
+        assert!(events.contains(&game::Event::Eliminated {
+            day,
+            positions: candidates,
+        }));
+        let lifted = events
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e,
+                    game::Event::Player(game::player::Event::Eliminated { .. })
+                )
+            })
+            .count();
+        assert_eq!(lifted, 2);
+    }
+
+    #[test]
+    fn abstainers_are_counted_for_the_last_nominee() {
+        let mut engine = dealt_engine();
+        let town = town_seats(&engine);
+        let day = engine.day;
+        engine.state = EngineState::Game(Activity::Evening(EveningActivity::Voting));
+        engine
+            .game
+            .player_by_position_mut(town[4])
+            .unwrap()
+            .mark_dead()
+            .unwrap();
+        engine.game.add_nomination(day, town[2], town[0]).unwrap();
+        engine.game.add_nomination(day, town[3], town[1]).unwrap();
+        engine.actor.reset(town[0]);
+        engine.apply(Command::Advance).unwrap();
+
+        let vote = |targets: &[Position]| Command::Vote {
+            targets: targets.to_vec(),
+        };
+        assert!(engine.apply(vote(&[town[2], town[2]])).is_err());
+        assert!(engine.apply(vote(&[town[2], town[4]])).is_err());
+        engine.apply(vote(&[town[2], town[3]])).unwrap();
+
+        // Reaching the last nominee hands them every remaining vote
+        let events = engine.apply(Command::Advance).unwrap();
+        let implicit = events
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e,
+                    Event::Game(game::Event::Voting(game::voting::Event::Voted { .. }))
+                )
+            })
+            .count();
+        assert_eq!(implicit, 7);
+        let voting = &engine.game.voting()[&day];
+        assert_eq!(voting.tally(), vec![(town[0], 2), (town[1], 7)]);
+
+        engine.apply(Command::Advance).unwrap();
+        assert!(!engine.game.player_by_position(town[1]).unwrap().is_alive());
+    }
+
+    #[test]
+    fn overtime_warns_a_silenced_speaker_after_the_shorter_limit() {
+        let mut rules = RuleSet::default();
+        rules.timers.overtime = Overtime::Warn;
+        let mut engine = started_engine_with(rules);
+        while engine.state == EngineState::Game(Activity::Night(NightActivity::RoleAssignment)) {
+            engine.apply(Command::Advance).unwrap();
+        }
+        let speaker = town_seats(&engine)[0];
+        for _ in 0..2 {
+            engine.apply(Command::Warn { target: speaker }).unwrap();
+        }
+        engine.state = EngineState::Game(Activity::Noon(NoonActivity::Discussion));
+        engine.actor.reset(speaker);
+
+        // Two warnings do not silence yet
+        let events = engine.apply(Command::Advance).unwrap();
+        assert!(events.contains(&Event::Timer(timer::Event::Started {
+            speaker,
+            seconds: 60
+        })));
+
+        engine.apply(Command::Warn { target: speaker }).unwrap();
+        engine.actor.reset(speaker);
+        let events = engine.apply(Command::Advance).unwrap();
+        assert!(events.contains(&Event::Timer(timer::Event::Started {
+            speaker,
+            seconds: 30
+        })));
+
+        engine.apply(Command::PauseTimer { remaining: 12 }).unwrap();
+        assert!(engine.apply(Command::ExpireTimer).is_err());
+        engine.apply(Command::ExtendTimer { seconds: 5 }).unwrap();
+        assert_eq!(
+            engine.apply(Command::ResumeTimer).unwrap(),
+            vec![Event::Timer(timer::Event::Resumed { remaining: 17 })]
+        );
+
+        let events = engine.apply(Command::ExpireTimer).unwrap();
+        assert_eq!(events[0], Event::Timer(timer::Event::Expired { speaker }));
+        // The fourth warning removes the speaker
+        assert!(
+            engine
+                .game
+                .player_by_position(speaker)
+                .unwrap()
+                .is_removed()
+        );
+        assert!(engine.apply(Command::ExpireTimer).is_err());
+    }
+
+    #[test]
+    fn players_can_join_at_a_pre_drawn_seat() {
+        let mut engine = Engine::new(RuleSet::default());
+        let join = |name: &str, seat: u8| Command::Join {
+            name: name.to_string(),
+            seat: Some(Position::new(seat)),
+        };
+
+        engine.apply(join("a", 4)).unwrap();
+        assert!(engine.apply(join("b", 4)).is_err());
+        assert!(engine.apply(join("b", 11)).is_err());
+        assert!(engine.game.player_by_name("b").is_none());
+
+        engine.apply(join("b", 7)).unwrap();
+        engine
+            .apply(Command::SwapSeats {
+                from: Position::new(4),
+                to: Position::new(7),
+            })
+            .unwrap();
+        engine
+            .apply(Command::SwapSeats {
+                from: Position::new(7),
+                to: Position::new(1),
+            })
+            .unwrap();
+
+        assert!(
+            engine
+                .apply(Command::SwapSeats {
+                    from: Position::new(1),
+                    to: Position::new(1),
+                })
+                .is_err()
+        );
+        let seat = |name: &str| engine.game.player_by_name(name).unwrap().position();
+        assert_eq!(seat("a"), Some(Position::new(1)));
+        assert_eq!(seat("b"), Some(Position::new(4)));
+        assert!(
+            engine
+                .game
+                .available_positions()
+                .contains(&Position::new(7))
+        );
+        assert!(
+            !engine
+                .game
+                .available_positions()
+                .contains(&Position::new(1))
+        );
+    }
+
+    #[test]
+    fn dealt_role_replaces_the_drawn_card() {
+        let mut engine = started_engine();
+        engine.apply(Command::Advance).unwrap();
+        let actor = engine.actor.current().unwrap();
+
+        engine.apply(Command::DealRole { role: Role::Don }).unwrap();
+        assert_eq!(
+            engine.game.player_by_position(actor).unwrap().role(),
+            Some(Role::Don)
+        );
+        assert_eq!(engine.game.available_roles().len(), 9);
+        assert!(!engine.game.available_roles().contains(&Role::Don));
+
+        engine.apply(Command::Advance).unwrap();
+        assert!(engine.apply(Command::DealRole { role: Role::Don }).is_err());
+    }
+
+    #[test]
+    fn resizing_the_table_reseats_displaced_players() {
+        let mut engine = Engine::new(RuleSet::default());
+        for name in ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"] {
+            engine
+                .apply(Command::Join {
+                    name: name.to_string(),
+                    seat: None,
+                })
+                .unwrap();
+        }
+
+        assert!(engine.apply(Command::ResizeTable { seats: 8 }).is_err());
+
+        engine.apply(Command::ResizeTable { seats: 12 }).unwrap();
+        assert_eq!(engine.state, EngineState::Lobby(LobbyStatus::Waiting));
+        assert_eq!(engine.game.available_positions().len(), 2);
+
+        for name in ["a", "b", "c"] {
+            engine
+                .apply(Command::Leave {
+                    name: name.to_string(),
+                })
+                .unwrap();
+        }
+        engine.apply(Command::ResizeTable { seats: 7 }).unwrap();
+
+        assert_eq!(engine.state, EngineState::Lobby(LobbyStatus::Ready));
+        assert!(engine.game.available_positions().is_empty());
+        assert!(
+            engine
+                .game
+                .players()
+                .iter()
+                .all(|p| p.position().is_some_and(|pos| pos.value() <= 7))
+        );
+        assert_eq!(engine.game.available_roles().len(), 7);
+    }
+
+    #[test]
+    fn restored_engine_matches_its_snapshot() {
+        let mut engine = started_engine();
+        for _ in 0..4 {
+            engine.apply(Command::Advance).unwrap();
+        }
+
+        let json = serde_json::to_string(&engine.snapshot()).unwrap();
+        let saved: snapshot::Engine = serde_json::from_str(&json).unwrap();
+        let restored = Engine::restore(&saved).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(restored.snapshot()).unwrap(),
+            serde_json::to_value(engine.snapshot()).unwrap()
+        );
+        assert_eq!(
+            restored.game.available_roles().len(),
+            engine.game.available_roles().len()
+        );
+        assert!(restored.game.available_positions().is_empty());
+    }
+
+    #[test]
+    fn replay_reproduces_seeded_draws() {
+        let mut engine = started_engine();
+        engine
+            .apply(Command::Warn {
+                target: Position::new(11),
+            })
+            .unwrap_err();
+        for _ in 0..12 {
+            engine.apply(Command::Advance).unwrap();
+        }
+
+        let json = serde_json::to_string(&snapshot::Save {
+            engine: engine.snapshot(),
+            journal: Some(engine.journal().clone()),
+            log: Vec::new(),
+        })
+        .unwrap();
+        let save: snapshot::Save = serde_json::from_str(&json).unwrap();
+        let replayed = Engine::replay(save.journal.as_ref().unwrap()).unwrap();
+
+        assert_eq!(replayed.journal().entries(), engine.journal().entries());
+        assert_eq!(
+            serde_json::to_value(replayed.snapshot()).unwrap(),
+            serde_json::to_value(engine.snapshot()).unwrap()
+        );
+    }
+
+    #[test]
+    fn replay_detects_divergence() {
+        let mut engine = Engine::with_seed(RuleSet::default(), 7);
+        engine
+            .apply(Command::Join {
+                name: "a".to_string(),
+                seat: None,
+            })
+            .unwrap();
+
+        let mut json = serde_json::to_value(engine.journal()).unwrap();
+        json["entries"][0]["command"]["Join"]["name"] = "b".into();
+        let journal: Journal = serde_json::from_value(json).unwrap();
+
+        assert!(matches!(
+            Engine::replay(&journal),
+            Err(Error::ReplayDiverged { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn undo_and_redo_roll_state_back_and_forth() {
+        let mut engine = started_engine();
+        engine.apply(Command::Advance).unwrap();
+        let before = serde_json::to_value(engine.snapshot()).unwrap();
+
+        engine
+            .apply(Command::Warn {
+                target: Position::new(1),
+            })
+            .unwrap();
+        let after = serde_json::to_value(engine.snapshot()).unwrap();
+
+        let reverted = engine.undo().unwrap();
+        assert!(reverted.iter().any(|event| matches!(
+            event,
+            Event::Game(game::Event::Player(game::player::Event::Warned { .. }))
+        )));
+        assert_eq!(serde_json::to_value(engine.snapshot()).unwrap(), before);
+
+        // The undone warning stays on record
+        let warned = |entry: &journal::Entry| matches!(entry.command, Command::Warn { .. });
+        let entries = engine.journal().entries();
+        assert!(entries.last().is_some_and(|e| warned(e) && e.reverted));
+
+        engine.redo().unwrap();
+        assert_eq!(serde_json::to_value(engine.snapshot()).unwrap(), after);
+        assert!(matches!(engine.redo(), Err(err) if err.to_string() == "Nothing to redo"));
+
+        let entries = engine.journal().entries();
+        assert_eq!(entries.iter().filter(|e| warned(e)).count(), 2);
+        let replayed = Engine::replay(engine.journal()).unwrap();
+        assert_eq!(serde_json::to_value(replayed.snapshot()).unwrap(), after);
+    }
+
+    #[test]
+    fn failed_redo_keeps_the_command() {
+        let mut engine = started_engine();
+        engine.apply(Command::Advance).unwrap();
+        engine.undo().unwrap();
+
+        engine.result = Some(GameResult {
+            winner: Team::Town,
+            reason: WinReason::MafiaEliminated,
+            day: engine.day,
+        });
+        assert!(engine.redo().is_err());
+
+        engine.result = None;
+        engine.redo().unwrap();
+        assert!(engine.actor.current().is_some());
+    }
+
+    #[test]
+    fn new_command_discards_redo_history() {
+        let mut engine = started_engine();
+        engine.apply(Command::Advance).unwrap();
+        engine.undo().unwrap();
+        engine.apply(Command::Advance).unwrap();
+
+        assert!(engine.redo().is_err());
+    }
+
+    #[test]
+    fn broken_turns_are_reported_instead_of_panicking() {
+        let mut engine = started_engine();
+        let error = |result: Result<Vec<Event>>| result.unwrap_err().downcast::<Error>().unwrap();
+
+        // Lobby commands reached past the allowed-command check
+        assert!(matches!(
+            error(engine.join("k", None)),
+            Error::InvalidLobbyStatus {
+                expected: LobbyStatus::Waiting,
+                ..
+            }
+        ));
+        assert!(matches!(engine.ensure_lobby(), Err(Error::NotInLobby(_))));
+
+        // Nobody has been called up to draw a card yet
+        assert!(matches!(
+            error(engine.apply(Command::RevokeRole)),
+            Error::NotAllowed(_)
+        ));
+        engine.apply(Command::Advance).unwrap();
+        engine.apply(Command::RevokeRole).unwrap();
+        assert!(matches!(
+            error(engine.revoke_role(Position::new(1))),
+            Error::NoRole(_)
+        ));
+        assert!(matches!(
+            error(engine.nominate(Position::new(2))),
+            Error::InvalidPhase {
+                expected: Activity::Noon(NoonActivity::Discussion),
+                actual: EngineState::Game(Activity::Night(NightActivity::RoleAssignment)),
+            }
+        ));
+
+        // A game put in the voting phase without a vote
+        engine.state = EngineState::Game(Activity::Evening(EveningActivity::Voting));
+        assert!(matches!(
+            error(engine.apply(Command::Advance)),
+            Error::NoVoting
+        ));
+    }
+
+    #[test]
+    fn only_allowed_commands_and_seats_are_accepted() {
+        let mut engine = dealt_engine();
+        let error = |result: Result<Vec<Event>>| result.unwrap_err().downcast::<Error>().unwrap();
+        // Reveals are only watched, though warnings go on
+        assert!(matches!(
+            engine.allowed_commands().as_slice(),
+            [
+                CommandKind::Advance,
+                CommandKind::Warn { .. },
+                CommandKind::Pardon { .. }
+            ]
+        ));
+
+        let town = town_seats(&engine);
+        engine
+            .game
+            .player_by_position_mut(town[0])
+            .unwrap()
+            .mark_dead()
+            .unwrap();
+        engine.state = EngineState::Game(Activity::Noon(NoonActivity::Discussion));
+        engine.actor.reset(town[1]);
+        engine.apply(Command::Advance).unwrap();
+
+        let nominate = engine
+            .allowed_commands()
+            .into_iter()
+            .find_map(|k| match k {
+                CommandKind::Nominate { targets } => Some(targets),
+                _ => None,
+            })
+            .unwrap();
+        assert!(!nominate.contains(&town[0]));
+        assert!(matches!(
+            error(engine.apply(Command::Nominate { target: town[0] })),
+            Error::SeatNotAllowed { seat, .. } if seat == town[0]
+        ));
+        // Day is no time for night actions
+        assert!(matches!(
+            error(engine.apply(Command::Shoot { target: town[2] })),
+            Error::NotAllowed("shoot")
+        ));
+        engine.apply(Command::Nominate { target: town[2] }).unwrap();
+
+        kill_where(&mut engine, Team::Mafia, 3);
+        engine.end();
+        assert!(engine.allowed_commands().is_empty());
+    }
+
+    #[test]
+    fn removed_don_still_opens_the_mafia_briefing() {
+        let mut engine = dealt_engine();
+        let don = engine.seat_of(Role::Don).unwrap();
+        engine
+            .game
+            .player_by_position_mut(don)
+            .unwrap()
+            .mark_removed()
+            .unwrap();
+        engine.state = EngineState::Game(Activity::Night(NightActivity::DonReveal));
+        engine.actor.reset(don);
+
+        engine.apply(Command::Advance).unwrap();
+        engine.apply(Command::Advance).unwrap();
+        assert_eq!(
+            engine.state,
+            EngineState::Game(Activity::Night(NightActivity::MafiaBriefing))
+        );
+    }
+
+    #[test]
+    fn restore_rejects_duplicated_seats() {
+        let engine = started_engine();
+        let mut saved = engine.snapshot();
+        saved.game.players[1].position = saved.game.players[0].position;
+
+        assert!(Engine::restore(&saved).is_err());
+    }
+
+    #[test]
+    fn restore_rejects_invalid_rules() {
+        let engine = started_engine();
+        let mut saved = engine.snapshot();
+        saved.game.rules.roles.push(rules::RoleCount {
+            role: Role::Mafia,
+            count: u8::MAX,
+        });
+
+        assert!(Engine::restore(&saved).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::domain::position::Position;
-use crate::snapshot::Snapshot;
+use crate::snapshot::{self, Restore, Snapshot};
 
 #[derive(Debug, Clone)]
 pub struct Actor {
@@ -9,10 +9,27 @@ pub struct Actor {
 }
 
 impl Snapshot for Actor {
-    type Output = Option<Position>;
+    type Output = snapshot::Actor;
 
     fn snapshot(&self) -> Self::Output {
-        self.current()
+        snapshot::Actor {
+            start: self.start,
+            current: self.current,
+            completed: self.completed,
+        }
+    }
+}
+
+impl Restore for Actor {
+    type Input = snapshot::Actor;
+    type Error = std::convert::Infallible;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        Ok(Self {
+            start: snapshot.start,
+            current: snapshot.current,
+            completed: snapshot.completed,
+        })
     }
 }
 
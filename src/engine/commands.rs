@@ -1,18 +1,189 @@
-use crate::domain::position::Position;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use crate::domain::{position::Position, role::Role};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Command {
-    Join { name: String },
-    Leave { name: String },
+    Join {
+        name: String,
+        /// Pre-drawn seat; a free seat is drawn at random when absent.
+        #[serde(default)]
+        seat: Option<Position>,
+    },
+    Leave {
+        name: String,
+    },
+    ResizeTable {
+        seats: u8,
+    },
+    SwapSeats {
+        from: Position,
+        to: Position,
+    },
+    Start,
+    Advance,
+    AssignRole,
+    RevokeRole,
+    /// Hands the current actor a specific card instead of a random one.
+    DealRole {
+        role: Role,
+    },
+    Warn {
+        target: Position,
+    },
+    Pardon {
+        target: Position,
+    },
+    Nominate {
+        target: Position,
+    },
+    Vote {
+        targets: Vec<Position>,
+    },
+    Shoot {
+        target: Position,
+    },
+    Check {
+        target: Position,
+    },
+    Heal {
+        target: Position,
+    },
+    Block {
+        target: Position,
+    },
+    Guess {
+        targets: Vec<Position>,
+    },
+    /// Stops the turn clock with `remaining` seconds left on it.
+    PauseTimer {
+        remaining: u64,
+    },
+    ResumeTimer,
+    ExtendTimer {
+        seconds: u64,
+    },
+    /// Reported when the turn clock runs out.
+    ExpireTimer,
+}
+
+/// A command the engine accepts in its current state, with the arguments
+/// it may take.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommandKind {
+    Join,
+    Leave,
+    ResizeTable,
+    SwapSeats,
     Start,
     Advance,
     AssignRole,
     RevokeRole,
-    Warn { target: Position },
-    Pardon { target: Position },
-    Nominate { target: Position },
-    Vote { targets: Vec<Position> },
-    Shoot { target: Position },
-    Check { target: Position },
+    DealRole { roles: Vec<Role> },
+    Warn { targets: Vec<Position> },
+    Pardon { targets: Vec<Position> },
+    Nominate { targets: Vec<Position> },
+    Vote { voters: Vec<Position> },
+    Shoot { targets: Vec<Position> },
+    Check { targets: Vec<Position> },
+    Heal { targets: Vec<Position> },
+    Block { targets: Vec<Position> },
     Guess { targets: Vec<Position> },
+    PauseTimer,
+    ResumeTimer,
+    ExtendTimer,
+    ExpireTimer,
+}
+
+impl Command {
+    /// What the command does, as in "Cannot {name} now".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Join { .. } => "join",
+            Command::Leave { .. } => "leave",
+            Command::ResizeTable { .. } => "resize the table",
+            Command::SwapSeats { .. } => "swap seats",
+            Command::Start => "start",
+            Command::Advance => "advance",
+            Command::AssignRole => "assign a role",
+            Command::RevokeRole => "revoke a role",
+            Command::DealRole { .. } => "deal a role",
+            Command::Warn { .. } => "warn",
+            Command::Pardon { .. } => "pardon",
+            Command::Nominate { .. } => "nominate",
+            Command::Vote { .. } => "vote",
+            Command::Shoot { .. } => "shoot",
+            Command::Check { .. } => "check",
+            Command::Heal { .. } => "heal",
+            Command::Block { .. } => "block",
+            Command::Guess { .. } => "guess",
+            Command::PauseTimer { .. } => "pause the timer",
+            Command::ResumeTimer => "resume the timer",
+            Command::ExtendTimer { .. } => "extend the timer",
+            Command::ExpireTimer => "expire the timer",
+        }
+    }
+
+    /// Seats the command names.
+    pub fn seats(&self) -> &[Position] {
+        match self {
+            Command::Warn { target }
+            | Command::Pardon { target }
+            | Command::Nominate { target }
+            | Command::Shoot { target }
+            | Command::Check { target }
+            | Command::Heal { target }
+            | Command::Block { target } => std::slice::from_ref(target),
+            Command::Vote { targets } | Command::Guess { targets } => targets,
+            _ => &[],
+        }
+    }
+}
+
+impl CommandKind {
+    /// Whether `command` is of this kind; its arguments are not looked at.
+    pub fn is_kind_of(&self, command: &Command) -> bool {
+        use CommandKind as Kind;
+        matches!(
+            (self, command),
+            (Kind::Join, Command::Join { .. })
+                | (Kind::Leave, Command::Leave { .. })
+                | (Kind::ResizeTable, Command::ResizeTable { .. })
+                | (Kind::SwapSeats, Command::SwapSeats { .. })
+                | (Kind::Start, Command::Start)
+                | (Kind::Advance, Command::Advance)
+                | (Kind::AssignRole, Command::AssignRole)
+                | (Kind::RevokeRole, Command::RevokeRole)
+                | (Kind::DealRole { .. }, Command::DealRole { .. })
+                | (Kind::Warn { .. }, Command::Warn { .. })
+                | (Kind::Pardon { .. }, Command::Pardon { .. })
+                | (Kind::Nominate { .. }, Command::Nominate { .. })
+                | (Kind::Vote { .. }, Command::Vote { .. })
+                | (Kind::Shoot { .. }, Command::Shoot { .. })
+                | (Kind::Check { .. }, Command::Check { .. })
+                | (Kind::Heal { .. }, Command::Heal { .. })
+                | (Kind::Block { .. }, Command::Block { .. })
+                | (Kind::Guess { .. }, Command::Guess { .. })
+                | (Kind::PauseTimer, Command::PauseTimer { .. })
+                | (Kind::ResumeTimer, Command::ResumeTimer)
+                | (Kind::ExtendTimer, Command::ExtendTimer { .. })
+                | (Kind::ExpireTimer, Command::ExpireTimer)
+        )
+    }
+
+    /// Seats the command may name; `None` when it names no seat.
+    pub fn seats(&self) -> Option<&[Position]> {
+        match self {
+            CommandKind::Warn { targets }
+            | CommandKind::Pardon { targets }
+            | CommandKind::Nominate { targets }
+            | CommandKind::Shoot { targets }
+            | CommandKind::Check { targets }
+            | CommandKind::Heal { targets }
+            | CommandKind::Block { targets }
+            | CommandKind::Guess { targets } => Some(targets),
+            CommandKind::Vote { voters } => Some(voters),
+            _ => None,
+        }
+    }
 }
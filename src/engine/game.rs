@@ -1,17 +1,20 @@
 pub mod check;
+pub mod final_vote;
+pub mod night;
 pub mod player;
 pub mod voting;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::{self};
 
-use crate::domain::{DayIndex, Position, Role};
+use crate::domain::{DayIndex, Position, Role, Team};
 use crate::engine::{
     Actor, Turn,
-    game::{player::Player, voting::Voting},
+    game::{final_vote::FinalVote, player::Player, voting::Voting},
+    rules::{self, RuleSet},
 };
-use crate::snapshot::{self, Snapshot};
+use crate::snapshot::{self, Restore, Snapshot};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -35,17 +38,47 @@ pub enum Error {
     #[error("No available positions left")]
     NoAvailablePositions,
 
+    #[error("Seat {0} is not free")]
+    SeatTaken(Position),
+
+    #[error("Seat {0} cannot be swapped with itself")]
+    SwapWithItself(Position),
+
+    #[error("No {0} card left in the pool")]
+    RoleUnavailable(Role),
+
+    #[error("{players} players have joined, too many for a table of {seats}")]
+    TooManyPlayers { players: usize, seats: u8 },
+
+    #[error("No more than {0} guesses are allowed")]
+    TooManyGuesses(u8),
+
+    #[error("{0} has already made a guess")]
+    AlreadyGuessed(Position),
+
+    #[error("{0} is guessed more than once")]
+    DuplicateGuess(Position),
+
+    #[error("{0} is not in the game and cannot be guessed")]
+    GuessNotAlive(Position),
+
     #[error(transparent)]
     Player(#[from] player::Error),
 
     #[error(transparent)]
     Check(#[from] check::Error),
 
+    #[error(transparent)]
+    Night(#[from] night::Error),
+
     #[error(transparent)]
     Voting(#[from] voting::Error),
+
+    #[error(transparent)]
+    Rules(#[from] rules::Error),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Event {
     PlayerLeft {
         name: String,
@@ -55,18 +88,30 @@ pub enum Event {
     },
     Player(player::Event),
     Check(check::Event),
+    Night(night::Event),
     Voting(voting::Event),
     FinalVoting(Position),
     MafiaKill {
         position: Position,
     },
+    /// The living mafia did not all shoot the same seat.
+    MafiaMissed,
     Guess {
-        position: Position,
+        by: Position,
+        targets: Vec<Position>,
+    },
+    /// How many mafia a best guess named, known once the game is over.
+    GuessEvaluated {
+        by: Position,
+        hits: u8,
     },
     Eliminated {
         day: DayIndex,
         positions: Vec<Position>,
     },
+    TableResized {
+        seats: u8,
+    },
 }
 
 impl fmt::Display for Event {
@@ -81,10 +126,23 @@ impl fmt::Display for Event {
             Event::Player(event) => write!(f, "{event}"),
             Event::Voting(event) => write!(f, "{event}"),
             Event::Check(event) => write!(f, "{event}"),
+            Event::Night(event) => write!(f, "{event}"),
             Event::MafiaKill { position } => {
                 write!(f, "Mafia has shoot {position}")
             }
-            Event::Guess { position } => write!(f, "Dead player has guessed {position}"),
+            Event::MafiaMissed => write!(f, "Mafia has missed"),
+            Event::Guess { by, targets } => write!(
+                f,
+                "{by} has guessed {}",
+                targets
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Event::GuessEvaluated { by, hits } => {
+                write!(f, "Best guess of {by} named {hits} mafia")
+            }
             Event::Eliminated { day, positions } => write!(
                 f,
                 "After day {} players at position {} were eliminated",
@@ -92,6 +150,7 @@ impl fmt::Display for Event {
                 positions.iter().map(|p| p.to_string()).collect::<String>()
             ),
             Event::FinalVoting(position) => write!(f, "Final Voting {position}"),
+            Event::TableResized { seats } => write!(f, "Table resized to {seats} seats"),
         }
     }
 }
@@ -101,20 +160,23 @@ pub struct Game {
     players: Vec<Player>,
     voting: HashMap<DayIndex, voting::Voting>,
     tie_voting: HashMap<DayIndex, voting::Voting>,
-    final_voting: HashMap<DayIndex, Vec<Position>>,
+    final_vote: HashMap<DayIndex, FinalVote>,
     check: HashMap<DayIndex, check::Check>,
+    night: HashMap<DayIndex, night::Night>,
+    /// Mafia kills; the Maniac's are kept with the rest of the night.
     kill: HashMap<DayIndex, Position>,
-    guess: Vec<Position>,
+    /// Best guesses by the seat that made them.
+    guesses: HashMap<Position, Vec<Position>>,
     eliminated: HashMap<DayIndex, Vec<Position>>,
     roles_pool: Vec<Role>,
     positions_pool: Vec<Position>,
+    rules: RuleSet,
 }
 
 #[derive(Debug, Clone)]
 pub enum Pool {
     Main,
     Tie,
-    Final,
 }
 
 impl Snapshot for Game {
@@ -133,31 +195,95 @@ impl Snapshot for Game {
                 .iter()
                 .map(|(k, v)| (k.current(), v.snapshot()))
                 .collect(),
-            final_voting: self
-                .final_voting
+            final_vote: self
+                .final_vote
                 .iter()
-                .map(|(k, v)| (k.current(), v.iter().map(|p| p.snapshot()).collect()))
+                .map(|(k, v)| (k.current(), v.snapshot()))
                 .collect(),
             check: self
                 .check
                 .iter()
                 .map(|(k, v)| (k.current(), v.snapshot()))
                 .collect(),
+            night: self
+                .night
+                .iter()
+                .map(|(k, v)| (k.current(), v.snapshot()))
+                .collect(),
             kill: self
                 .kill
                 .iter()
                 .map(|(k, v)| (k.current(), v.snapshot()))
                 .collect(),
-            guess: self.guess.clone(),
+            guesses: self.guesses.clone(),
             eliminated: self
                 .eliminated
                 .iter()
                 .map(|(k, v)| (k.current(), v.iter().map(|p| p.snapshot()).collect()))
                 .collect(),
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+impl Restore for Game {
+    type Input = snapshot::Game;
+    type Error = Error;
+
+    /// Rebuilds the game from a save. Seat and role pools are not stored;
+    /// they are recomputed by drawing every saved seat and role from fresh
+    /// pools, which also rejects saves with duplicated seats or roles.
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        snapshot.rules.validate()?;
+        let mut game = Game::new(snapshot.rules.clone());
+
+        for saved in &snapshot.players {
+            if game.player_by_name(&saved.name).is_some() {
+                return Err(Error::PlayerByNameAlreadyExist(saved.name.clone()));
+            }
+            if let Some(position) = saved.position {
+                game.take_position(position)?;
+            }
+            if let Some(role) = saved.role {
+                game.take_role(role)?;
+            }
+            let Ok(player) = Player::restore(saved);
+            game.players.push(player);
         }
+
+        game.voting = restore_by_day(&snapshot.voting);
+        game.tie_voting = restore_by_day(&snapshot.tie_voting);
+        game.check = restore_by_day(&snapshot.check);
+        game.night = restore_by_day(&snapshot.night);
+        game.final_vote = restore_by_day(&snapshot.final_vote);
+        game.kill = by_day(&snapshot.kill);
+        game.eliminated = by_day(&snapshot.eliminated);
+        game.guesses = snapshot.guesses.clone();
+
+        Ok(game)
     }
 }
 
+fn by_day<T: Clone>(saved: &HashMap<usize, T>) -> HashMap<DayIndex, T> {
+    saved
+        .iter()
+        .map(|(day, value)| (DayIndex::new(*day), value.clone()))
+        .collect()
+}
+
+fn restore_by_day<T>(saved: &HashMap<usize, T::Input>) -> HashMap<DayIndex, T>
+where
+    T: Restore<Error = std::convert::Infallible>,
+{
+    saved
+        .iter()
+        .map(|(day, value)| {
+            let Ok(value) = T::restore(value);
+            (DayIndex::new(*day), value)
+        })
+        .collect()
+}
+
 impl Turn for Game {
     fn next_actor<F>(&self, actor: &mut Actor, is_eligible: F) -> Option<Position>
     where
@@ -206,48 +332,71 @@ impl Turn for Game {
 }
 
 impl Game {
-    pub const PLAYER_COUNT: u8 = 10;
-
-    pub fn new() -> Self {
-        let mut positions_pool = Vec::new();
-        let players = Vec::with_capacity(Self::PLAYER_COUNT as usize);
+    pub fn new(rules: RuleSet) -> Self {
+        let players = Vec::with_capacity(rules.player_count as usize);
         let voting = HashMap::new();
         let tie_voting = HashMap::new();
-        let final_voting = HashMap::new();
+        let final_vote = HashMap::new();
         let check = HashMap::new();
+        let night = HashMap::new();
         let kill = HashMap::new();
-        let guess = Vec::new();
+        let guesses = HashMap::new();
         let eliminated = HashMap::new();
 
-        for pos in 1..=Self::PLAYER_COUNT {
-            positions_pool.push(Position::new(pos));
-        }
-
-        let roles_pool = vec![
-            Role::Don,
-            Role::Mafia,
-            Role::Mafia,
-            Role::Sheriff,
-            Role::Citizen,
-            Role::Citizen,
-            Role::Citizen,
-            Role::Citizen,
-            Role::Citizen,
-            Role::Citizen,
-        ];
+        let positions_pool = (1..=rules.player_count).map(Position::new).collect();
+        let roles_pool = rules.roles_pool();
 
         Self {
             players,
             voting,
             tie_voting,
-            final_voting,
+            final_vote,
             check,
+            night,
             kill,
-            guess,
+            guesses,
             eliminated,
             roles_pool,
             positions_pool,
+            rules,
+        }
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Switches to `rules` before the game starts, rebuilding the seat and
+    /// role pools. Players seated outside the new table lose their seat.
+    pub fn set_rules(&mut self, rules: RuleSet) -> Result<Vec<Event>, Error> {
+        if self.players.len() > rules.player_count as usize {
+            return Err(Error::TooManyPlayers {
+                players: self.players.len(),
+                seats: rules.player_count,
+            });
+        }
+
+        let mut events = Vec::new();
+        for player in &mut self.players {
+            if player
+                .position()
+                .is_some_and(|p| p.value() > rules.player_count)
+            {
+                events.extend(player.revoke_position()?.into_iter().map(Event::Player));
+            }
         }
+
+        self.positions_pool = (1..=rules.player_count)
+            .map(Position::new)
+            .filter(|p| self.player_by_position(*p).is_none())
+            .collect();
+        self.roles_pool = rules.roles_pool();
+        events.push(Event::TableResized {
+            seats: rules.player_count,
+        });
+        self.rules = rules;
+
+        Ok(events)
     }
 
     // ---------------- Players ----------------
@@ -258,7 +407,7 @@ impl Game {
         if self.players.iter().any(|p| p.name() == name) {
             return Err(Error::PlayerByNameAlreadyExist(name.to_string()));
         }
-        if self.players.len() >= Self::PLAYER_COUNT as usize {
+        if self.players.len() >= self.rules.player_count as usize {
             return Err(Error::Player(player::Error::HasPosition));
         }
 
@@ -287,12 +436,6 @@ impl Game {
         &mut self.players
     }
 
-    pub fn alive_players(&self) -> usize {
-        self.players
-            .iter()
-            .fold(0, |c, p| if p.is_alive() { c + 1 } else { c })
-    }
-
     pub fn player_by_position(&self, position: Position) -> Option<&Player> {
         self.players.iter().find(|p| p.position() == Some(position))
     }
@@ -303,6 +446,12 @@ impl Game {
             .find(|p| p.position() == Some(position))
     }
 
+    fn player_index_by_position(&self, position: Position) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|p| p.position() == Some(position))
+    }
+
     pub fn player_by_name(&self, name: &str) -> Option<&Player> {
         self.players.iter().find(|p| p.name() == name)
     }
@@ -311,16 +460,6 @@ impl Game {
         self.players.iter_mut().find(|p| p.name() == name)
     }
 
-    pub fn sheriff(&self) -> Option<&Player> {
-        self.players
-            .iter()
-            .find(|p| p.role() == Some(Role::Sheriff))
-    }
-
-    pub fn don(&self) -> Option<&Player> {
-        self.players.iter().find(|p| p.role() == Some(Role::Don))
-    }
-
     /*---------------- Checks ---------------- */
     pub fn record_sheriff_check(
         &mut self,
@@ -348,6 +487,90 @@ impl Game {
         Ok(events.into_iter().map(Event::Check).collect())
     }
 
+    pub fn record_block(&mut self, day: DayIndex, chair: Position) -> Result<Vec<Event>, Error> {
+        let events = self.night.entry(day).or_default().record_block(chair)?;
+        Ok(events.into_iter().map(Event::Night).collect())
+    }
+
+    pub fn record_heal(&mut self, day: DayIndex, chair: Position) -> Result<Vec<Event>, Error> {
+        let events = self.night.entry(day).or_default().record_heal(chair)?;
+        Ok(events.into_iter().map(Event::Night).collect())
+    }
+
+    pub fn record_maniac_shot(
+        &mut self,
+        day: DayIndex,
+        chair: Position,
+    ) -> Result<Vec<Event>, Error> {
+        let events = self
+            .night
+            .entry(day)
+            .or_default()
+            .record_maniac_shot(chair)?;
+        Ok(events.into_iter().map(Event::Night).collect())
+    }
+
+    /// Whether `position` sleeps through the rest of the night of `day`.
+    pub fn is_blocked(&self, day: DayIndex, position: Position) -> bool {
+        self.night
+            .get(&day)
+            .is_some_and(|n| n.blocked() == Some(position))
+    }
+
+    /// Carries out the kills of the night once every role has acted: a
+    /// healed seat survives every shot, and the Maniac kills on their own.
+    pub fn resolve_night(
+        &mut self,
+        day: DayIndex,
+        mafia_target: Option<Position>,
+    ) -> Result<Vec<Event>, Error> {
+        let night = self.night.get(&day).cloned().unwrap_or_default();
+        let mut events = Vec::new();
+        let saved = |events: &mut Vec<Event>, position| {
+            let event = Event::Night(night::Event::Saved { position });
+            if !events.contains(&event) {
+                events.push(event);
+            }
+        };
+
+        match mafia_target {
+            Some(target) if night.healed() == Some(target) => saved(&mut events, target),
+            Some(target) => {
+                events.extend(self.mark_dead(target)?);
+                events.extend(self.record_mafia_kill(day, target)?);
+            }
+            None => events.push(Event::MafiaMissed),
+        }
+
+        if let Some(target) = night.maniac_shot() {
+            if night.healed() == Some(target) {
+                saved(&mut events, target);
+            } else {
+                events.extend(self.mark_dead(target)?);
+                let night = self.night.entry(day).or_default();
+                events.extend(
+                    night
+                        .record_maniac_kill(target)
+                        .into_iter()
+                        .map(Event::Night),
+                );
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Marks `position` dead unless an earlier shot already has.
+    fn mark_dead(&mut self, position: Position) -> Result<Vec<Event>, Error> {
+        let player = self
+            .player_by_position_mut(position)
+            .ok_or(Error::PlayerByPositionNotFound(position))?;
+        if !player.is_alive() {
+            return Ok(Vec::new());
+        }
+        Ok(player.mark_dead()?.into_iter().map(Event::Player).collect())
+    }
+
     pub fn record_shoot(
         &mut self,
         day: DayIndex,
@@ -385,9 +608,58 @@ impl Game {
         }])
     }
 
-    pub fn record_guess(&mut self, guess: Position) -> Result<Vec<Event>, Error> {
-        self.guess.push(guess);
-        Ok(vec![Event::Guess { position: guess }])
+    /// Records the best guess of `by`: up to the allowed number of
+    /// distinct seats still in the game, made once.
+    pub fn record_guess(
+        &mut self,
+        by: Position,
+        targets: &[Position],
+    ) -> Result<Vec<Event>, Error> {
+        let max = self.rules.best_guess.max_guesses;
+        if targets.len() > max as usize {
+            return Err(Error::TooManyGuesses(max));
+        }
+        if self.guesses.contains_key(&by) {
+            return Err(Error::AlreadyGuessed(by));
+        }
+        for (i, target) in targets.iter().enumerate() {
+            if targets[..i].contains(target) {
+                return Err(Error::DuplicateGuess(*target));
+            }
+            if !self
+                .player_by_position(*target)
+                .is_some_and(|p| p.is_alive())
+            {
+                return Err(Error::GuessNotAlive(*target));
+            }
+        }
+
+        self.guesses.insert(by, targets.to_vec());
+        Ok(vec![Event::Guess {
+            by,
+            targets: targets.to_vec(),
+        }])
+    }
+
+    /// Scores every best guess against the actual mafia, in seat order.
+    pub fn evaluate_guesses(&self) -> Vec<Event> {
+        let mut guessers: Vec<&Position> = self.guesses.keys().collect();
+        guessers.sort();
+
+        guessers
+            .into_iter()
+            .map(|by| {
+                let hits = self.guesses[by]
+                    .iter()
+                    .filter(|target| {
+                        self.player_by_position(**target)
+                            .and_then(|p| p.team())
+                            .is_some_and(|team| team == Team::Mafia)
+                    })
+                    .count() as u8;
+                Event::GuessEvaluated { by: *by, hits }
+            })
+            .collect()
     }
 
     /* ---------------- Votes & Nominations ---------------- */
@@ -411,6 +683,8 @@ impl Game {
         Ok(events.into_iter().map(Event::Voting).collect())
     }
 
+    /// Records `voters` for `nominee`. The whole batch is rejected if anyone
+    /// in it is listed twice, has already voted or is out of the game.
     pub fn add_vote_batch(
         &mut self,
         day: DayIndex,
@@ -418,6 +692,13 @@ impl Game {
         nominee: Position,
         voters: &[Position],
     ) -> Result<Vec<Event>, Error> {
+        if let Some(voter) = voters
+            .iter()
+            .find(|v| !self.player_by_position(**v).is_some_and(|p| p.is_alive()))
+        {
+            return Err(voting::Error::InvalidVoter(*voter).into());
+        }
+
         match pool {
             Pool::Main => {
                 let voting = self.voting.entry(day).or_insert(Voting::new(
@@ -426,7 +707,7 @@ impl Game {
                         .filter_map(|p| if p.is_alive() { p.position() } else { None })
                         .collect(),
                 ));
-                let events = voting.batch_vote(nominee, voters, false)?;
+                let events = voting.batch_vote(nominee, voters, true)?;
                 Ok(events.into_iter().map(Event::Voting).collect())
             }
             Pool::Tie => {
@@ -436,21 +717,83 @@ impl Game {
                         .filter_map(|p| if p.is_alive() { p.position() } else { None })
                         .collect(),
                 ));
-                let events = voting.batch_vote(nominee, voters, false)?;
+                let events = voting.batch_vote(nominee, voters, true)?;
                 Ok(events.into_iter().map(Event::Voting).collect())
             }
-            Pool::Final => {
-                let mut events = Vec::new();
-                let voting = self.final_voting.entry(day).or_default();
-                for &voter in voters {
-                    if !voting.contains(&voter) {
-                        voting.push(voter);
-                        events.push(Event::FinalVoting(voter));
-                    }
-                }
-                Ok(events)
+        }
+    }
+
+    /// Opens the "lift all" vote on `candidates`; every living player votes.
+    pub fn open_final_vote(&mut self, day: DayIndex, candidates: Vec<Position>) {
+        let voters = self
+            .players
+            .iter()
+            .filter_map(|p| if p.is_alive() { p.position() } else { None })
+            .collect();
+        self.final_vote
+            .insert(day, FinalVote::new(candidates, voters));
+    }
+
+    /// Records yes votes to lift all candidates. The whole batch is rejected
+    /// if anyone in it cannot vote or has already voted.
+    pub fn add_final_votes(
+        &mut self,
+        day: DayIndex,
+        voters: &[Position],
+    ) -> Result<Vec<Event>, Error> {
+        let vote = self
+            .final_vote
+            .get(&day)
+            .ok_or(voting::Error::NoFinalVote)?;
+        for (i, &voter) in voters.iter().enumerate() {
+            let alive = self.player_by_position(voter).is_some_and(|p| p.is_alive());
+            if !alive || !vote.is_voter(voter) {
+                return Err(voting::Error::InvalidVoter(voter).into());
+            }
+            if voters[..i].contains(&voter) || vote.yes().contains(&voter) {
+                return Err(voting::Error::AlreadyVoted(voter).into());
             }
         }
+
+        let vote = self.final_vote.get_mut(&day).expect("checked above");
+        Ok(voters
+            .iter()
+            .filter(|&&voter| vote.vote_yes(voter))
+            .map(|&voter| Event::FinalVoting(voter))
+            .collect())
+    }
+
+    /// Whether the day's "lift all" vote reached a majority of the players
+    /// still in the game.
+    pub fn final_vote_passes(&self, day: DayIndex) -> bool {
+        self.final_vote.get(&day).is_some_and(|vote| {
+            !vote.candidates().is_empty()
+                && vote.passes(|voter| self.player_by_position(voter).is_some_and(|p| p.is_alive()))
+        })
+    }
+
+    /// Eliminates every candidate of the day's final vote still in the game.
+    pub fn lift_all(&mut self, day: DayIndex) -> Result<Vec<Event>, Error> {
+        let candidates = self
+            .final_vote
+            .get(&day)
+            .map(|vote| vote.candidates().to_vec())
+            .unwrap_or_default();
+
+        let mut events = Vec::new();
+        let mut lifted = Vec::new();
+        for candidate in candidates {
+            if let Some(player) = self
+                .player_by_position_mut(candidate)
+                .filter(|p| p.is_alive())
+            {
+                events.extend(player.mark_eliminated()?.into_iter().map(Event::Player));
+                lifted.push(candidate);
+            }
+        }
+        events.extend(self.record_eliminated(day, &lifted)?);
+
+        Ok(events)
     }
 
     pub fn voting(&self) -> &HashMap<DayIndex, Voting> {
@@ -469,14 +812,21 @@ impl Game {
         &mut self.tie_voting
     }
 
-    pub fn final_voting(&self) -> &HashMap<DayIndex, Vec<Position>> {
-        &self.final_voting
-    }
-
     pub fn get_kill(&self, day: DayIndex) -> Option<&Position> {
         self.kill.get(&day)
     }
 
+    /// Everyone killed in the night of `day`, the mafia's victim first.
+    pub fn killed(&self, day: DayIndex) -> Vec<Position> {
+        let mut killed: Vec<Position> = self.kill.get(&day).copied().into_iter().collect();
+        if let Some(position) = self.night.get(&day).and_then(|n| n.maniac_kill())
+            && !killed.contains(&position)
+        {
+            killed.push(position);
+        }
+        killed
+    }
+
     pub fn get_eliminated(&self, day: DayIndex) -> Option<&Vec<Position>> {
         self.eliminated.get(&day)
     }
@@ -511,6 +861,32 @@ impl Game {
         }
     }
 
+    /// Moves the player seated at `from` to `to`, swapping with whoever
+    /// sits there. A free `to` seat is taken from the pool.
+    pub fn swap_seats(&mut self, from: Position, to: Position) -> Result<Vec<Event>, Error> {
+        if from == to {
+            return Err(Error::SwapWithItself(from));
+        }
+        let mover = self
+            .player_index_by_position(from)
+            .ok_or(Error::PlayerByPositionNotFound(from))?;
+        let other = self.player_index_by_position(to);
+        if other.is_none() {
+            self.take_position(to)?;
+            self.return_position(from);
+        }
+
+        let mut events = Vec::new();
+        events.extend(self.players[mover].revoke_position()?);
+        if let Some(other) = other {
+            events.extend(self.players[other].revoke_position()?);
+            events.extend(self.players[other].assign_position(from)?);
+        }
+        events.extend(self.players[mover].assign_position(to)?);
+
+        Ok(events.into_iter().map(Event::Player).collect())
+    }
+
     pub fn return_position(&mut self, position: Position) {
         if !self.positions_pool.contains(&position) {
             self.positions_pool.push(position);
@@ -1,9 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::{
     domain::position::Position,
-    snapshot::{self, Snapshot},
+    snapshot::{self, Restore, Snapshot},
 };
 use thiserror::Error;
 
@@ -18,13 +18,25 @@ impl Snapshot for Check {
     }
 }
 
+impl Restore for Check {
+    type Input = snapshot::Check;
+    type Error = std::convert::Infallible;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sheriff: snapshot.sheriff,
+            don: snapshot.don,
+        })
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct Check {
     sheriff: Option<Position>,
     don: Option<Position>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Event {
     SheriffChecked { chair: Position },
     DonChecked { chair: Position },
@@ -0,0 +1,117 @@
+use crate::{
+    domain::position::Position,
+    snapshot::{self, Restore, Snapshot},
+};
+
+/// "Lift all" vote held when the tie vote did not break the tie: a single
+/// yes/no question whether every tied candidate leaves the game together.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FinalVote {
+    candidates: Vec<Position>,
+    /// Players allowed to vote when the vote opened
+    voters: Vec<Position>,
+    yes: Vec<Position>,
+}
+
+impl Snapshot for FinalVote {
+    type Output = snapshot::FinalVote;
+
+    fn snapshot(&self) -> Self::Output {
+        snapshot::FinalVote {
+            candidates: self.candidates.clone(),
+            voters: self.voters.clone(),
+            yes: self.yes.clone(),
+            no: self.no(),
+        }
+    }
+}
+
+impl Restore for FinalVote {
+    type Input = snapshot::FinalVote;
+    type Error = std::convert::Infallible;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        Ok(Self {
+            candidates: snapshot.candidates.clone(),
+            voters: snapshot.voters.clone(),
+            yes: snapshot.yes.clone(),
+        })
+    }
+}
+
+impl FinalVote {
+    pub fn new(candidates: Vec<Position>, mut voters: Vec<Position>) -> Self {
+        voters.sort();
+        Self {
+            candidates,
+            voters,
+            yes: Vec::new(),
+        }
+    }
+
+    pub fn candidates(&self) -> &[Position] {
+        &self.candidates
+    }
+
+    pub fn yes(&self) -> &[Position] {
+        &self.yes
+    }
+
+    /// Everyone who could vote and did not vote yes.
+    pub fn no(&self) -> Vec<Position> {
+        self.voters
+            .iter()
+            .filter(|v| !self.yes.contains(v))
+            .copied()
+            .collect()
+    }
+
+    pub fn is_voter(&self, position: Position) -> bool {
+        self.voters.contains(&position)
+    }
+
+    /// Records a yes vote. Returns `false` if `voter` already voted yes.
+    pub fn vote_yes(&mut self, voter: Position) -> bool {
+        if self.yes.contains(&voter) {
+            return false;
+        }
+        self.yes.push(voter);
+        true
+    }
+
+    /// Whether a majority of the players still voting said yes. Players
+    /// removed since the vote opened no longer count either way.
+    pub fn passes<F>(&self, still_voting: F) -> bool
+    where
+        F: Fn(Position) -> bool,
+    {
+        let voters = self.voters.iter().filter(|v| still_voting(**v)).count();
+        let yes = self.yes.iter().filter(|v| still_voting(**v)).count();
+        yes * 2 > voters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(value: u8) -> Position {
+        Position::new(value)
+    }
+
+    #[test]
+    fn majority_of_remaining_voters_lifts_all() {
+        let mut vote = FinalVote::new(vec![pos(2), pos(5)], (1..=6).map(pos).collect());
+        for voter in [1, 2, 3] {
+            assert!(vote.vote_yes(pos(voter)));
+        }
+        assert!(!vote.vote_yes(pos(1)));
+
+        // Three of six is not a majority
+        assert!(!vote.passes(|_| true));
+        assert_eq!(vote.no(), vec![pos(4), pos(5), pos(6)]);
+
+        // With seat 6 removed, three of five is
+        assert!(vote.passes(|p| p != pos(6)));
+    }
+}
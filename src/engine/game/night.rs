@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    domain::position::Position,
+    snapshot::{self, Restore, Snapshot},
+};
+use thiserror::Error;
+
+impl Snapshot for Night {
+    type Output = snapshot::Night;
+
+    fn snapshot(&self) -> Self::Output {
+        snapshot::Night {
+            blocked: self.blocked,
+            healed: self.healed,
+            maniac_shot: self.maniac_shot,
+            maniac_kill: self.maniac_kill,
+        }
+    }
+}
+
+impl Restore for Night {
+    type Input = snapshot::Night;
+    type Error = std::convert::Infallible;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        Ok(Self {
+            blocked: snapshot.blocked,
+            healed: snapshot.healed,
+            maniac_shot: snapshot.maniac_shot,
+            maniac_kill: snapshot.maniac_kill,
+        })
+    }
+}
+
+/// Actions of the single-actor night roles, Sheriff and Don aside.
+#[derive(Debug, Default, Clone)]
+pub struct Night {
+    blocked: Option<Position>,
+    healed: Option<Position>,
+    maniac_shot: Option<Position>,
+    /// Known once the night is over.
+    maniac_kill: Option<Position>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Event {
+    Blocked {
+        chair: Position,
+    },
+    Healed {
+        chair: Position,
+    },
+    ManiacShot {
+        chair: Position,
+    },
+    /// A shot at a healed seat.
+    Saved {
+        position: Position,
+    },
+    ManiacKill {
+        position: Position,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Blocked { chair } => write!(f, "Prostitute blocked {chair}"),
+            Event::Healed { chair } => write!(f, "Doctor healed {chair}"),
+            Event::ManiacShot { chair } => write!(f, "Maniac shot {chair}"),
+            Event::Saved { position } => write!(f, "Doctor saved {position}"),
+            Event::ManiacKill { position } => write!(f, "Maniac has killed {position}"),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Prostitute has already blocked a chair")]
+    AlreadyBlocked,
+
+    #[error("Doctor has already healed a chair")]
+    AlreadyHealed,
+
+    #[error("Maniac has already shot a chair")]
+    ManiacAlreadyShot,
+}
+
+impl Night {
+    pub fn record_block(&mut self, chair: Position) -> Result<Vec<Event>, Error> {
+        if self.blocked.is_some() {
+            return Err(Error::AlreadyBlocked);
+        }
+
+        self.blocked = Some(chair);
+        Ok(vec![Event::Blocked { chair }])
+    }
+
+    pub fn record_heal(&mut self, chair: Position) -> Result<Vec<Event>, Error> {
+        if self.healed.is_some() {
+            return Err(Error::AlreadyHealed);
+        }
+
+        self.healed = Some(chair);
+        Ok(vec![Event::Healed { chair }])
+    }
+
+    pub fn record_maniac_shot(&mut self, chair: Position) -> Result<Vec<Event>, Error> {
+        if self.maniac_shot.is_some() {
+            return Err(Error::ManiacAlreadyShot);
+        }
+
+        self.maniac_shot = Some(chair);
+        Ok(vec![Event::ManiacShot { chair }])
+    }
+
+    pub fn record_maniac_kill(&mut self, position: Position) -> Vec<Event> {
+        self.maniac_kill = Some(position);
+        vec![Event::ManiacKill { position }]
+    }
+
+    pub fn blocked(&self) -> Option<Position> {
+        self.blocked
+    }
+
+    pub fn healed(&self) -> Option<Position> {
+        self.healed
+    }
+
+    pub fn maniac_shot(&self) -> Option<Position> {
+        self.maniac_shot
+    }
+
+    pub fn maniac_kill(&self) -> Option<Position> {
+        self.maniac_kill
+    }
+}
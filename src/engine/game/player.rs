@@ -1,10 +1,11 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 
 use crate::domain::DayIndex;
-use crate::domain::{position::Position, role::Role, status::Status};
-use crate::snapshot::{self, Snapshot};
+use crate::domain::{position::Position, role::Role, status::Status, team::Team};
+use crate::engine::rules::WarningPenalties;
+use crate::snapshot::{self, Restore, Snapshot};
 
 #[derive(Debug, Clone, Default)]
 struct Penalty {
@@ -35,7 +36,7 @@ pub enum Error {
     NoWarnings,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Event {
     PositionAssigned {
         name: String,
@@ -205,6 +206,30 @@ impl Snapshot for Player {
     }
 }
 
+impl Restore for Player {
+    type Input = snapshot::Player;
+    type Error = std::convert::Infallible;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: snapshot.name.clone(),
+            role: snapshot.role,
+            position: snapshot.position,
+            shots: snapshot.shots.as_ref().map(|shots| {
+                shots
+                    .iter()
+                    .map(|(day, target)| (DayIndex::new(*day), *target))
+                    .collect()
+            }),
+            warnings: snapshot.warnings,
+            penalty: Penalty {
+                silenced: snapshot.is_silenced,
+            },
+            status: snapshot.status,
+        })
+    }
+}
+
 impl Default for Player {
     fn default() -> Self {
         Self {
@@ -293,7 +318,7 @@ impl Player {
     }
 
     // ----------- Warnings ----------
-    pub fn warn(&mut self) -> Result<Vec<Event>, Error> {
+    pub fn warn(&mut self, penalties: WarningPenalties) -> Result<Vec<Event>, Error> {
         if self.status == Status::Removed {
             return Err(Error::MaxWarningsReached);
         }
@@ -307,24 +332,20 @@ impl Player {
 
         self.warnings += 1;
 
-        match self.warnings {
-            3 => {
-                self.penalty.silenced = true;
-                events.push(Event::Silenced {
-                    name: self.name.clone(),
-                    position: self.position.unwrap(),
-                });
-            }
-            4 => {
-                self.mark_removed()?;
-            }
-            _ => {}
+        if self.warnings == penalties.silence_at {
+            self.penalty.silenced = true;
+            events.push(Event::Silenced {
+                name: self.name.clone(),
+                position: self.position.unwrap(),
+            });
+        } else if self.warnings == penalties.remove_at {
+            self.mark_removed()?;
         }
 
         Ok(events)
     }
 
-    pub fn pardon(&mut self) -> Result<Vec<Event>, Error> {
+    pub fn pardon(&mut self, penalties: WarningPenalties) -> Result<Vec<Event>, Error> {
         if self.warnings == 0 {
             return Err(Error::NoWarnings);
         }
@@ -338,7 +359,7 @@ impl Player {
             total: self.warnings,
         });
 
-        if self.warnings < 3 && self.penalty.silenced {
+        if self.warnings < penalties.silence_at && self.penalty.silenced {
             self.penalty.silenced = false;
             events.push(Event::Unsilenced {
                 name: self.name.clone(),
@@ -346,7 +367,7 @@ impl Player {
             });
         }
 
-        if self.status == Status::Removed && self.warnings < 4 {
+        if self.status == Status::Removed && self.warnings < penalties.remove_at {
             self.restore_alive()?;
         }
 
@@ -433,6 +454,10 @@ impl Player {
         matches!(self.role, Some(Role::Don) | Some(Role::Mafia))
     }
 
+    pub fn team(&self) -> Option<Team> {
+        self.role.map(|role| role.team())
+    }
+
     pub fn is_sheriff(&self) -> bool {
         matches!(self.role, Some(Role::Sheriff))
     }
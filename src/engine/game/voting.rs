@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     fmt,
@@ -7,7 +7,7 @@ use std::{
 use crate::{
     domain::position::Position,
     engine::{Actor, Turn},
-    snapshot::{self, Snapshot},
+    snapshot::{self, Restore, Snapshot},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,7 +22,7 @@ pub struct Voting {
     nominations: HashMap<Position, Position>, // nominator -> nominee
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Event {
     Nominated {
         nominator: Position,
@@ -78,6 +78,12 @@ pub enum Error {
 
     #[error("Voter {0:?} is not allowed to vote")]
     InvalidVoter(Position),
+
+    #[error("No final vote is open")]
+    NoFinalVote,
+
+    #[error("{cast} votes were cast by {voters} voters")]
+    VoteCountMismatch { cast: usize, voters: usize },
 }
 
 impl Snapshot for Voting {
@@ -101,10 +107,37 @@ impl Snapshot for Voting {
                     )
                 })
                 .collect(),
+            voters: sorted(&self.voters),
+            remaining_voters: sorted(&self.remaining_voters),
+            remaining_nominees: sorted(&self.remaining_nominees),
+            tally: self.tally(),
         }
     }
 }
 
+impl Restore for Voting {
+    type Input = snapshot::Voting;
+    type Error = std::convert::Infallible;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        Ok(Self {
+            nominees: snapshot.nominees.clone(),
+            remaining_nominees: snapshot.remaining_nominees.iter().copied().collect(),
+            voters: snapshot.voters.iter().copied().collect(),
+            remaining_voters: snapshot.remaining_voters.iter().copied().collect(),
+            votes: snapshot.votes.clone(),
+            nominations: snapshot.nominations.clone(),
+        })
+    }
+}
+
+/// Sets have no stable order, so snapshots list their members by seat.
+fn sorted(positions: &HashSet<Position>) -> Vec<Position> {
+    let mut positions: Vec<Position> = positions.iter().copied().collect();
+    positions.sort();
+    positions
+}
+
 impl Turn for Voting {
     fn next_actor<F>(&self, actor: &mut Actor, is_eligible: F) -> Option<Position>
     where
@@ -211,6 +244,26 @@ impl Voting {
         self.remaining_voters.clone()
     }
 
+    /// Votes received by each nominee, in nomination order.
+    pub fn tally(&self) -> Vec<(Position, usize)> {
+        self.nominees
+            .iter()
+            .map(|n| (*n, self.votes.get(n).map_or(0, Vec::len)))
+            .collect()
+    }
+
+    /// Checks that every voter has voted exactly once.
+    pub fn ensure_counted(&self) -> Result<(), Error> {
+        let cast = self.votes.values().map(Vec::len).sum();
+        if cast != self.voters.len() {
+            return Err(Error::VoteCountMismatch {
+                cast,
+                voters: self.voters.len(),
+            });
+        }
+        Ok(())
+    }
+
     pub fn finalize_nominee(&mut self, nominee: Position) -> Option<Event> {
         if self.remaining_nominees.remove(&nominee) && !self.votes.contains_key(&nominee) {
             return Some(Event::Skipped { nominee });
@@ -218,6 +271,8 @@ impl Voting {
         None
     }
 
+    /// Per sport rules, everyone who has not voted votes for the last
+    /// nominee.
     pub fn cast_implicit_votes_for_nominee(&mut self) -> Vec<Event> {
         let mut events = Vec::new();
 
@@ -226,7 +281,8 @@ impl Voting {
             .last()
             .copied()
             .expect("At least one nominee exists");
-        let voters: Vec<_> = self.remaining_voters.drain().collect();
+        let voters = sorted(&self.remaining_voters);
+        self.remaining_voters.clear();
 
         for voter in voters {
             self.votes.entry(nominee).or_default().push(voter);
@@ -292,6 +348,19 @@ impl Voting {
             return Err(Error::InvalidNominee(nominee));
         }
 
+        // A strict batch is checked as a whole before any vote is recorded
+        if strict {
+            let mut seen_in_batch = HashSet::new();
+            for &voter in voters {
+                if !self.voters.contains(&voter) {
+                    return Err(Error::InvalidVoter(voter));
+                }
+                if !seen_in_batch.insert(voter) || !self.remaining_voters.contains(&voter) {
+                    return Err(Error::AlreadyVoted(voter));
+                }
+            }
+        }
+
         let mut events = Vec::new();
         let mut seen_in_batch = HashSet::new();
 
@@ -550,6 +619,23 @@ mod tests {
         assert_eq!(winners, vec![nominee_a]);
     }
 
+    #[test]
+    fn abstainers_vote_for_the_last_nominee() {
+        let mut voting = Voting::from_nominees(&[pos(4), pos(5)], create_voters(5));
+
+        voting.batch_vote(pos(4), &[pos(1), pos(2)], true).unwrap();
+        assert!(matches!(
+            voting.ensure_counted(),
+            Err(Error::VoteCountMismatch { cast: 2, voters: 5 })
+        ));
+
+        let events = voting.cast_implicit_votes_for_nominee();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(voting.tally(), vec![(pos(4), 2), (pos(5), 3)]);
+        assert!(voting.ensure_counted().is_ok());
+    }
+
     #[test]
     fn winners_returns_all_nominees_in_case_of_tie() {
         let voters = create_voters(10);
@@ -821,9 +907,9 @@ mod tests {
         let err = voting.batch_vote(nominee, &batch, true).unwrap_err();
         assert!(matches!(err, Error::InvalidVoter(v) if v == invalid_voter));
 
-        let votes_for_nominee = voting.votes.get(&nominee).unwrap();
-        assert_eq!(votes_for_nominee.len(), 1); // only voter_1 applied
-        assert!(votes_for_nominee.contains(&voter_1));
+        // The batch is rejected as a whole
+        assert!(!voting.votes.contains_key(&nominee));
+        assert!(voting.is_eligible(voter_1));
     }
 
     #[test]
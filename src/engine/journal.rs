@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Event, commands::Command, rules::RuleSet};
+use crate::snapshot;
+
+/// What applying a command produced.
+///
+/// Rejected commands are journaled too: a failing command may still have
+/// touched state, and replay has to reject it at the same point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Applied(Vec<Event>),
+    Rejected(String),
+}
+
+impl From<&anyhow::Result<Vec<Event>>> for Outcome {
+    fn from(result: &anyhow::Result<Vec<Event>>) -> Self {
+        match result {
+            Ok(events) => Outcome::Applied(events.clone()),
+            Err(err) => Outcome::Rejected(err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub command: Command,
+    pub outcome: Outcome,
+    /// Rolled back by an undo; kept for the record, skipped by replay.
+    #[serde(default)]
+    pub reverted: bool,
+}
+
+/// Append-only record of every command applied to an engine. Undone
+/// commands stay in it, marked as reverted.
+///
+/// Together with the RNG seed and rules (or the snapshot the engine was
+/// restored from, if any) it is enough to rebuild the engine command by
+/// command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Journal {
+    seed: u64,
+    rules: RuleSet,
+    base: Option<Box<snapshot::Engine>>,
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    pub fn new(seed: u64, rules: RuleSet, base: Option<snapshot::Engine>) -> Self {
+        Self {
+            seed,
+            rules,
+            base: base.map(Box::new),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, command: Command, result: &anyhow::Result<Vec<Event>>) {
+        self.entries.push(Entry {
+            command,
+            outcome: result.into(),
+            reverted: false,
+        });
+    }
+
+    /// Appends an entry as it was recorded elsewhere.
+    pub fn push(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    /// Marks every entry from `index` onwards as reverted.
+    pub fn revert_from(&mut self, index: usize) {
+        for entry in &mut self.entries[index..] {
+            entry.reverted = true;
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    pub fn base(&self) -> Option<&snapshot::Engine> {
+        self.base.as_deref()
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::domain::{DayIndex, Team};
+
+/// Outcome of a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameResult {
+    pub winner: Team,
+    pub reason: WinReason,
+    pub day: DayIndex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WinReason {
+    /// Every Mafia player, and the Maniac if any, is dead, eliminated or
+    /// removed.
+    MafiaEliminated,
+    /// Living Mafia players are at least as many as the rest of the table,
+    /// and no Maniac is left to stop them.
+    MafiaParity,
+    /// The Maniac is alive with at most one other player.
+    ManiacStanding,
+    /// The Jester was voted out.
+    JesterVotedOut,
+}
+
+impl fmt::Display for WinReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinReason::MafiaEliminated => write!(f, "all mafia are out"),
+            WinReason::MafiaParity => write!(f, "mafia reached parity with the town"),
+            WinReason::ManiacStanding => write!(f, "the maniac outlasted the table"),
+            WinReason::JesterVotedOut => write!(f, "the jester was voted out"),
+        }
+    }
+}
+
+/// The table as the win predicates see it.
+#[derive(Debug, Default)]
+pub struct Standing {
+    alive: HashMap<Team, usize>,
+    voted_out: HashSet<Team>,
+}
+
+impl Standing {
+    pub fn record(&mut self, team: Team, alive: bool, voted_out: bool) {
+        if alive {
+            *self.alive.entry(team).or_default() += 1;
+        }
+        if voted_out {
+            self.voted_out.insert(team);
+        }
+    }
+
+    fn alive(&self, team: Team) -> usize {
+        self.alive.get(&team).copied().unwrap_or_default()
+    }
+
+    fn alive_total(&self) -> usize {
+        self.alive.values().sum()
+    }
+}
+
+impl Team {
+    /// Order the win predicates are judged in; the first team to win takes
+    /// the game.
+    pub const PRIORITY: [Team; 4] = [Team::Jester, Team::Maniac, Team::Mafia, Team::Town];
+
+    /// Whether the team has won, and why.
+    pub fn wins(self, standing: &Standing) -> Option<WinReason> {
+        let mafia = standing.alive(Team::Mafia);
+        let maniac = standing.alive(Team::Maniac);
+        let won = match self {
+            Team::Jester => standing.voted_out.contains(&Team::Jester),
+            Team::Maniac => maniac > 0 && standing.alive_total() <= 2,
+            Team::Mafia => mafia > 0 && maniac == 0 && mafia * 2 >= standing.alive_total(),
+            Team::Town => mafia == 0 && maniac == 0,
+        };
+        won.then_some(match self {
+            Team::Jester => WinReason::JesterVotedOut,
+            Team::Maniac => WinReason::ManiacStanding,
+            Team::Mafia => WinReason::MafiaParity,
+            Team::Town => WinReason::MafiaEliminated,
+        })
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} wins on day {}, {}",
+            self.winner, self.day, self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn winner(table: &[(Team, bool)]) -> Option<(Team, WinReason)> {
+        let mut standing = Standing::default();
+        for &(team, alive) in table {
+            standing.record(team, alive, false);
+        }
+        Team::PRIORITY
+            .into_iter()
+            .find_map(|team| team.wins(&standing).map(|reason| (team, reason)))
+    }
+
+    #[test]
+    fn maniac_holds_off_the_mafia_until_one_opponent_is_left() {
+        use Team::*;
+
+        let parity = [(Mafia, true), (Mafia, true), (Town, true), (Town, true)];
+        assert_eq!(winner(&parity), Some((Mafia, WinReason::MafiaParity)));
+
+        let with_maniac = [(Mafia, true), (Mafia, true), (Maniac, true), (Town, true)];
+        assert_eq!(winner(&with_maniac), None);
+
+        let last_two = [(Mafia, true), (Maniac, true), (Town, false)];
+        assert_eq!(winner(&last_two), Some((Maniac, WinReason::ManiacStanding)));
+
+        let killers_out = [(Mafia, false), (Maniac, false), (Town, true)];
+        assert_eq!(
+            winner(&killers_out),
+            Some((Town, WinReason::MafiaEliminated))
+        );
+    }
+}
@@ -0,0 +1,451 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use crate::domain::{
+    Activity, EveningActivity, MorningActivity, NightActivity, NoonActivity, Role, Team, ability,
+};
+
+/// Table rules a game is played under.
+///
+/// Defaults follow the classic 10 seat sport mafia rules. Clubs running
+/// other federation rules load their own set from a JSON file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleSet {
+    pub player_count: u8,
+    /// Special roles in play; every remaining seat is a Citizen.
+    pub roles: Vec<RoleCount>,
+    pub warnings: WarningPenalties,
+    pub first_day_single_nominee: SingleNominee,
+    pub best_guess: BestGuess,
+    pub timers: Timers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleCount {
+    pub role: Role,
+    pub count: u8,
+}
+
+/// Number of warnings at which a player is silenced and removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WarningPenalties {
+    pub silence_at: u8,
+    pub remove_at: u8,
+}
+
+/// What happens when exactly one player is nominated on the first day.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SingleNominee {
+    /// No vote is held and the game moves on to the night.
+    #[default]
+    Skip,
+    /// The nominee is voted on like any other day.
+    Vote,
+}
+
+/// Best guess of the player shot on the first night.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BestGuess {
+    pub enabled: bool,
+    pub max_guesses: u8,
+}
+
+/// Turn clocks, started whenever the actor changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Timers {
+    /// Activities without a limit are not timed.
+    pub limits: Vec<ActivityLimit>,
+    /// Shorter limit for the speeches of a silenced player.
+    pub silenced: Option<u64>,
+    /// What the engine does when a speech runs out of time.
+    pub overtime: Overtime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityLimit {
+    pub activity: Activity,
+    pub seconds: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Overtime {
+    /// The host decides what to do.
+    #[default]
+    Nothing,
+    /// The speaker gets a warning.
+    Warn,
+    /// The turn passes to the next actor.
+    Advance,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(
+        "Tables of {0} players are not supported, expected {min} to {max}",
+        min = RuleSet::MIN_PLAYERS,
+        max = RuleSet::MAX_PLAYERS
+    )]
+    UnsupportedPlayerCount(u8),
+
+    #[error("Player count must be at least {min}, got {actual}")]
+    TooFewPlayers { min: u16, actual: u8 },
+
+    #[error("Rules must have exactly one {0}")]
+    RoleCountInvalid(Role),
+
+    #[error("Rules allow at most one {0}")]
+    RoleNotUnique(Role),
+
+    #[error("Citizens are implied by the player count and cannot be listed")]
+    CitizensListed,
+
+    #[error("Mafia must be outnumbered by the town at the start")]
+    MafiaMajority,
+
+    #[error("Players must be silenced before they are removed")]
+    InvalidWarningPenalties,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            player_count: 10,
+            roles: vec![
+                RoleCount {
+                    role: Role::Don,
+                    count: 1,
+                },
+                RoleCount {
+                    role: Role::Mafia,
+                    count: 2,
+                },
+                RoleCount {
+                    role: Role::Sheriff,
+                    count: 1,
+                },
+            ],
+            warnings: WarningPenalties::default(),
+            first_day_single_nominee: SingleNominee::Skip,
+            best_guess: BestGuess::default(),
+            timers: Timers::default(),
+        }
+    }
+}
+
+impl Default for Timers {
+    fn default() -> Self {
+        use Activity::*;
+
+        let limit = |activity, seconds| ActivityLimit { activity, seconds };
+        Self {
+            limits: vec![
+                limit(Night(NightActivity::SheriffReveal), 5),
+                limit(Night(NightActivity::DonReveal), 5),
+                limit(Night(NightActivity::MafiaBriefing), 60),
+                limit(Night(NightActivity::ProstituteBlock), 10),
+                limit(Night(NightActivity::ManiacKill), 10),
+                limit(Night(NightActivity::DoctorHeal), 10),
+                limit(Night(NightActivity::SheriffCheck), 10),
+                limit(Night(NightActivity::DonCheck), 10),
+                limit(Morning(MorningActivity::Guessing), 15),
+                limit(Morning(MorningActivity::DeathSpeech), 60),
+                limit(Noon(NoonActivity::Discussion), 60),
+                limit(Evening(EveningActivity::Voting), 2),
+                limit(Evening(EveningActivity::TieDiscussion), 30),
+                limit(Evening(EveningActivity::TieVoting), 2),
+                limit(Evening(EveningActivity::FinalVoting), 2),
+                limit(Evening(EveningActivity::FinalSpeech), 60),
+            ],
+            silenced: Some(30),
+            overtime: Overtime::Nothing,
+        }
+    }
+}
+
+impl Timers {
+    /// Limit of a turn in `activity`, if it is timed. Silenced players only
+    /// get the shorter limit for speeches.
+    pub fn limit(&self, activity: Activity, silenced: bool) -> Option<u64> {
+        let seconds = self
+            .limits
+            .iter()
+            .find(|l| l.activity == activity)
+            .map(|l| l.seconds)?;
+        match self.silenced {
+            Some(short) if silenced && super::timer::is_speech(activity) => {
+                Some(seconds.min(short))
+            }
+            _ => Some(seconds),
+        }
+    }
+}
+
+impl Default for WarningPenalties {
+    fn default() -> Self {
+        Self {
+            silence_at: 3,
+            remove_at: 4,
+        }
+    }
+}
+
+impl Default for BestGuess {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_guesses: 3,
+        }
+    }
+}
+
+impl RuleSet {
+    pub const MIN_PLAYERS: u8 = 7;
+    pub const MAX_PLAYERS: u8 = 12;
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        let rules: RuleSet = serde_json::from_str(&json)?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(Self::MIN_PLAYERS..=Self::MAX_PLAYERS).contains(&self.player_count) {
+            return Err(Error::UnsupportedPlayerCount(self.player_count));
+        }
+        // The night sequence wakes exactly one Don and one Sheriff.
+        for role in [Role::Don, Role::Sheriff] {
+            if self.count(role) != 1 {
+                return Err(Error::RoleCountInvalid(role));
+            }
+        }
+        if self.roles.iter().any(|r| r.role == Role::Citizen) {
+            return Err(Error::CitizensListed);
+        }
+        // Single-actor night turns wake one player
+        for r in &self.roles {
+            if r.role.spec().unique && self.count(r.role) > 1 {
+                return Err(Error::RoleNotUnique(r.role));
+            }
+        }
+
+        // Sums are widened and saturate so a crafted file cannot overflow them
+        let min = self.count_special().saturating_add(1);
+        if u16::from(self.player_count) < min {
+            return Err(Error::TooFewPlayers {
+                min,
+                actual: self.player_count,
+            });
+        }
+
+        let mafia: u16 = self
+            .roles
+            .iter()
+            .filter(|r| r.role.team() == Team::Mafia)
+            .fold(0, |count, r| count.saturating_add(r.count.into()));
+        if mafia.saturating_mul(2) >= u16::from(self.player_count) {
+            return Err(Error::MafiaMajority);
+        }
+
+        if self.warnings.silence_at >= self.warnings.remove_at {
+            return Err(Error::InvalidWarningPenalties);
+        }
+
+        Ok(())
+    }
+
+    /// The same rules for a table of `player_count` seats. The mafia team
+    /// (Don included) is scaled to a third of the table; Citizens fill the rest.
+    pub fn resized(&self, player_count: u8) -> Result<Self, Error> {
+        let mut rules = self.clone();
+        rules.player_count = player_count;
+
+        let mafia = (player_count / 3).saturating_sub(self.count(Role::Don));
+        match rules.roles.iter_mut().find(|r| r.role == Role::Mafia) {
+            Some(r) => r.count = mafia,
+            None => rules.roles.push(RoleCount {
+                role: Role::Mafia,
+                count: mafia,
+            }),
+        }
+
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn count(&self, role: Role) -> u8 {
+        match role {
+            Role::Citizen => {
+                u8::try_from(u16::from(self.player_count).saturating_sub(self.count_special()))
+                    .unwrap_or_default()
+            }
+            _ => self
+                .roles
+                .iter()
+                .filter(|r| r.role == role)
+                .fold(0, |count, r| count.saturating_add(r.count)),
+        }
+    }
+
+    /// Full role deck for a game, special roles first then Citizens.
+    pub fn roles_pool(&self) -> Vec<Role> {
+        let mut pool = Vec::with_capacity(self.player_count as usize);
+        for r in &self.roles {
+            pool.extend(std::iter::repeat_n(r.role, r.count as usize));
+        }
+        pool.extend(std::iter::repeat_n(
+            Role::Citizen,
+            self.count(Role::Citizen) as usize,
+        ));
+        pool
+    }
+
+    /// Night turns after the first day, generated from the roles in play.
+    pub fn night_sequence(&self) -> Vec<NightActivity> {
+        ability::night_sequence(self.roles.iter().filter(|r| r.count > 0).map(|r| r.role))
+    }
+
+    fn count_special(&self) -> u16 {
+        self.roles
+            .iter()
+            .fold(0, |count, r| count.saturating_add(r.count.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rules_are_classic() {
+        let rules = RuleSet::default();
+
+        assert!(rules.validate().is_ok());
+        assert_eq!(rules.roles_pool().len(), 10);
+        assert_eq!(rules.count(Role::Citizen), 6);
+        assert_eq!(rules.count(Role::Mafia), 2);
+    }
+
+    #[test]
+    fn bundled_classic_rules_match_defaults() {
+        let rules: RuleSet =
+            serde_json::from_str(include_str!("../../rules/classic.json")).unwrap();
+
+        assert_eq!(rules, RuleSet::default());
+    }
+
+    #[test]
+    fn partial_json_falls_back_to_defaults() {
+        let rules: RuleSet = serde_json::from_str(r#"{ "warnings": { "remove_at": 5 } }"#).unwrap();
+
+        assert_eq!(rules.player_count, 10);
+        assert_eq!(rules.warnings.silence_at, 3);
+        assert_eq!(rules.warnings.remove_at, 5);
+    }
+
+    #[test]
+    fn silenced_speakers_get_the_shorter_limit() {
+        let timers = Timers::default();
+        let discussion = Activity::Noon(NoonActivity::Discussion);
+        let check = Activity::Night(NightActivity::SheriffCheck);
+
+        assert_eq!(timers.limit(discussion, false), Some(60));
+        assert_eq!(timers.limit(discussion, true), Some(30));
+        assert_eq!(timers.limit(check, true), Some(10));
+        assert_eq!(
+            timers.limit(Activity::Night(NightActivity::RoleAssignment), false),
+            None
+        );
+    }
+
+    #[test]
+    fn mafia_majority_is_rejected() {
+        let rules = RuleSet {
+            player_count: 7,
+            roles: vec![
+                RoleCount {
+                    role: Role::Don,
+                    count: 1,
+                },
+                RoleCount {
+                    role: Role::Mafia,
+                    count: 3,
+                },
+                RoleCount {
+                    role: Role::Sheriff,
+                    count: 1,
+                },
+            ],
+            ..RuleSet::default()
+        };
+
+        assert!(matches!(rules.validate(), Err(Error::MafiaMajority)));
+    }
+
+    #[test]
+    fn oversized_role_counts_are_rejected_without_overflow() {
+        let mut rules = RuleSet::default();
+        rules.roles.push(RoleCount {
+            role: Role::Mafia,
+            count: u8::MAX,
+        });
+        assert!(matches!(rules.validate(), Err(Error::TooFewPlayers { .. })));
+    }
+
+    #[test]
+    fn casual_roles_join_the_night_once_each() {
+        let mut rules = RuleSet::default();
+        rules.roles.push(RoleCount {
+            role: Role::Doctor,
+            count: 1,
+        });
+
+        assert!(rules.validate().is_ok());
+        assert_eq!(
+            rules.night_sequence(),
+            vec![
+                NightActivity::MafiaShooting,
+                NightActivity::DoctorHeal,
+                NightActivity::SheriffCheck,
+                NightActivity::DonCheck
+            ]
+        );
+
+        rules.roles.last_mut().unwrap().count = 2;
+        assert!(matches!(
+            rules.validate(),
+            Err(Error::RoleNotUnique(Role::Doctor))
+        ));
+    }
+
+    #[test]
+    fn resizing_scales_the_mafia_team() {
+        let rules = RuleSet::default();
+
+        for (players, mafia) in [(7, 1), (8, 1), (9, 2), (10, 2), (11, 2), (12, 3)] {
+            let resized = rules.resized(players).unwrap();
+            assert_eq!(resized.count(Role::Mafia), mafia);
+            assert_eq!(resized.count(Role::Don), 1);
+            assert_eq!(resized.count(Role::Sheriff), 1);
+            assert_eq!(resized.roles_pool().len(), players as usize);
+        }
+    }
+
+    #[test]
+    fn unsupported_table_sizes_are_rejected() {
+        let rules = RuleSet::default();
+
+        assert!(matches!(
+            rules.resized(6),
+            Err(Error::UnsupportedPlayerCount(6))
+        ));
+        assert!(matches!(
+            rules.resized(13),
+            Err(Error::UnsupportedPlayerCount(13))
+        ));
+    }
+}
@@ -0,0 +1,476 @@
+//! Test harness for the engine state machine: scripted scenarios on a
+//! dealt table, and seeded random command runs checked against invariants.
+
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use super::{Command, CommandKind, Engine, Event, game, result::WinReason, rules};
+use crate::domain::{
+    Activity, EngineState, EveningActivity, MorningActivity, NightActivity, NoonActivity, Position,
+    Role, Team,
+};
+use crate::snapshot::Snapshot;
+
+/// Most advances a scenario takes to reach a phase before giving up.
+const MAX_ADVANCES: usize = 200;
+/// Commands sent in each random game.
+const FUZZ_STEPS: usize = 400;
+
+/// A game driven step by step, each step checked as it goes.
+///
+/// Steps panic on a surprise, so a failing scenario points at the step that
+/// went wrong.
+struct Scenario {
+    engine: Engine,
+    /// Events of the last step.
+    events: Vec<Event>,
+}
+
+impl Scenario {
+    /// Ten players at seats 1 to 10, dealt `roles` by seat and Citizens
+    /// everywhere else. Play stops once the cards are dealt.
+    fn dealt(roles: &[(u8, Role)]) -> Self {
+        let mut rules = rules::RuleSet::default();
+        rules.roles.clear();
+        for &(_, role) in roles {
+            match rules.roles.iter_mut().find(|r| r.role == role) {
+                Some(r) => r.count += 1,
+                None => rules.roles.push(rules::RoleCount { role, count: 1 }),
+            }
+        }
+        rules.validate().expect("fixture should be a valid deck");
+
+        let mut engine = Engine::with_seed(rules, 0);
+        for seat in 1..=10u8 {
+            engine
+                .apply(Command::Join {
+                    name: format!("p{seat}"),
+                    seat: Some(Position::new(seat)),
+                })
+                .unwrap();
+        }
+        engine.apply(Command::Start).unwrap();
+        while engine.state == EngineState::Game(Activity::Night(NightActivity::RoleAssignment)) {
+            engine.apply(Command::Advance).unwrap();
+            let Some(seat) = engine.actor.current() else {
+                continue;
+            };
+            let role = roles
+                .iter()
+                .find(|(s, _)| Position::new(*s) == seat)
+                .map_or(Role::Citizen, |(_, role)| *role);
+            if engine.state == EngineState::Game(Activity::Night(NightActivity::RoleAssignment)) {
+                engine.apply(Command::DealRole { role }).unwrap();
+            }
+        }
+
+        Self {
+            engine,
+            events: Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, command: Command) -> &mut Self {
+        match self.engine.apply(command.clone()) {
+            Ok(events) => self.events = events,
+            Err(err) => panic!("{command:?} failed in {:?}: {err}", self.engine.state),
+        }
+        self
+    }
+
+    fn rejects(&mut self, command: Command) -> &mut Self {
+        let before = serde_json::to_value(self.engine.snapshot()).unwrap();
+        assert!(
+            self.engine.apply(command.clone()).is_err(),
+            "{command:?} should fail in {:?}",
+            self.engine.state
+        );
+        let after = serde_json::to_value(self.engine.snapshot()).unwrap();
+        assert_eq!(before, after, "rejected {command:?} changed the game");
+        self
+    }
+
+    fn advance(&mut self) -> &mut Self {
+        self.apply(Command::Advance)
+    }
+
+    /// Advances until `phase` begins, collecting the events on the way.
+    fn advance_to(&mut self, phase: Activity) -> &mut Self {
+        let mut events = Vec::new();
+        for _ in 0..MAX_ADVANCES {
+            self.advance();
+            events.append(&mut self.events);
+            if self.engine.state == EngineState::Game(phase) {
+                self.events = events;
+                return self;
+            }
+        }
+        panic!("{phase} never began, stuck in {:?}", self.engine.state);
+    }
+
+    /// Sends `command` for each seat as the actor reaches it, until the
+    /// phase is over.
+    fn each_actor(&mut self, mut command: impl FnMut(Position) -> Option<Command>) -> &mut Self {
+        let phase = self.engine.state;
+        let mut events = Vec::new();
+        for _ in 0..MAX_ADVANCES {
+            self.advance();
+            events.append(&mut self.events);
+            if self.engine.state != phase {
+                self.events = events;
+                return self;
+            }
+            if let Some(cmd) = self.engine.actor.current().and_then(&mut command) {
+                self.apply(cmd);
+                events.append(&mut self.events);
+            }
+        }
+        panic!("{phase:?} never ended");
+    }
+
+    fn expect_phase(&mut self, phase: Activity) -> &mut Self {
+        assert_eq!(self.engine.state, EngineState::Game(phase));
+        self
+    }
+
+    fn expect_actor(&mut self, seat: u8) -> &mut Self {
+        assert_eq!(self.engine.actor.current(), Some(Position::new(seat)));
+        self
+    }
+
+    fn expect_event(&mut self, event: Event) -> &mut Self {
+        assert!(
+            self.events.contains(&event),
+            "{event:?} missing from {:#?}",
+            self.events
+        );
+        self
+    }
+
+    fn expect_no_event(&mut self, unwanted: impl Fn(&Event) -> bool) -> &mut Self {
+        if let Some(event) = self.events.iter().find(|e| unwanted(e)) {
+            panic!("unexpected {event:?}");
+        }
+        self
+    }
+
+    fn expect_alive(&mut self, seats: &[u8], alive: bool) -> &mut Self {
+        for &seat in seats {
+            let player = self
+                .engine
+                .game
+                .player_by_position(Position::new(seat))
+                .unwrap();
+            assert_eq!(player.is_alive(), alive, "seat {seat}");
+        }
+        self
+    }
+}
+
+fn seat(value: u8) -> Position {
+    Position::new(value)
+}
+
+fn night(turn: NightActivity) -> Activity {
+    Activity::Night(turn)
+}
+
+const CLASSIC: [(u8, Role); 4] = [
+    (1, Role::Don),
+    (2, Role::Mafia),
+    (3, Role::Mafia),
+    (4, Role::Sheriff),
+];
+
+/// A command the host could send at any time, with seats that may or may
+/// not make sense.
+fn random_command(rng: &mut StdRng) -> Command {
+    let seat = |rng: &mut StdRng| Position::new(rng.random_range(0..=11));
+    let command = match rng.random_range(0..21) {
+        0 => Command::Warn { target: seat(rng) },
+        1 => Command::Pardon { target: seat(rng) },
+        2 | 3 => Command::Nominate { target: seat(rng) },
+        4 | 5 => Command::Shoot { target: seat(rng) },
+        6 => Command::Check { target: seat(rng) },
+        7 => Command::Heal { target: seat(rng) },
+        8 => Command::Block { target: seat(rng) },
+        9 => Command::Guess {
+            targets: vec![seat(rng), seat(rng), seat(rng)],
+        },
+        10 | 11 => Command::Vote {
+            targets: (0..rng.random_range(0..5))
+                .map(|_| Position::new(rng.random_range(1..=10)))
+                .collect(),
+        },
+        12 => Command::PauseTimer {
+            remaining: rng.random_range(0..90),
+        },
+        13 => Command::ResumeTimer,
+        14 => Command::ExtendTimer { seconds: 30 },
+        15 => Command::ExpireTimer,
+        16 => Command::AssignRole,
+        17 => Command::RevokeRole,
+        18 => Command::DealRole {
+            role: *[Role::Citizen, Role::Don, Role::Sheriff, Role::Jester]
+                .choose(rng)
+                .unwrap(),
+        },
+        _ => Command::Advance,
+    };
+    // Most of the time the host just moves the game on
+    if rng.random_bool(0.5) {
+        Command::Advance
+    } else {
+        command
+    }
+}
+
+/// Invariants that hold after every command, accepted or not.
+fn check_invariants(engine: &Engine, before: &HashSet<Position>, events: &[Event]) {
+    let players = engine.game.players();
+    let dons = players.iter().filter(|p| p.is_don()).count();
+    if players.iter().all(|p| p.role().is_some()) {
+        assert_eq!(dons, 1, "exactly one Don once the cards are dealt");
+    } else {
+        assert!(dons <= 1, "at most one Don while dealing");
+    }
+
+    // `before` holds the seats that were alive when the command was sent
+    for event in events {
+        match event {
+            Event::Game(game::Event::Voting(game::voting::Event::Voted { voter, .. })) => {
+                assert!(before.contains(voter), "{voter} voted while out");
+            }
+            Event::Game(game::Event::Voting(game::voting::Event::Nominated {
+                nominator,
+                nominee,
+            })) => {
+                assert!(
+                    before.contains(nominator),
+                    "{nominator} nominated while out"
+                );
+                assert!(before.contains(nominee), "{nominee} nominated while out");
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(current) = engine.actor.current()
+        && let EngineState::Game(Activity::Night(
+            NightActivity::MafiaShooting | NightActivity::SheriffCheck | NightActivity::DonCheck,
+        )) = engine.state
+    {
+        let actor = engine.game.player_by_position(current).unwrap();
+        assert!(actor.is_alive(), "{current} woke up while out");
+    }
+}
+
+/// Plays a game of random commands drawn from `seed`.
+fn fuzz_game(seed: u64, roles: &[(u8, Role)]) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut scenario = Scenario::dealt(roles);
+    let engine = &mut scenario.engine;
+    let mut result = None;
+
+    for _ in 0..FUZZ_STEPS {
+        let command = random_command(&mut rng);
+        let alive: HashSet<Position> = engine
+            .game
+            .players()
+            .iter()
+            .filter(|p| p.is_alive())
+            .filter_map(|p| p.position())
+            .collect();
+
+        let events = engine.apply(command).unwrap_or_default();
+        check_invariants(engine, &alive, &events);
+
+        let allowed = engine.allowed_commands();
+        assert_eq!(
+            allowed.contains(&CommandKind::Advance),
+            engine.result.is_none(),
+            "only a decided game cannot move on"
+        );
+        if result.is_some() {
+            assert_eq!(engine.result, result, "a decided game stays decided");
+        }
+        result = engine.result;
+    }
+}
+
+#[test]
+fn first_day_runs_from_dealing_to_the_first_kill() {
+    use EveningActivity::*;
+    use NightActivity::*;
+
+    let mut game = Scenario::dealt(&CLASSIC);
+    game.expect_phase(night(SheriffReveal))
+        .advance_to(night(MafiaBriefing))
+        .advance_to(Activity::Noon(NoonActivity::Discussion))
+        .advance()
+        .expect_actor(1)
+        .rejects(Command::Vote {
+            targets: vec![seat(2)],
+        })
+        .rejects(Command::Shoot { target: seat(5) })
+        .apply(Command::Nominate { target: seat(5) })
+        .expect_event(Event::Game(game::Event::Voting(
+            game::voting::Event::Nominated {
+                nominator: seat(1),
+                nominee: seat(5),
+            },
+        )))
+        .rejects(Command::Nominate { target: seat(6) })
+        .advance()
+        .expect_actor(2)
+        .apply(Command::Nominate { target: seat(6) })
+        .advance_to(Activity::Evening(Voting))
+        .advance()
+        .expect_actor(5)
+        .rejects(Command::Vote {
+            targets: vec![seat(1), seat(1)],
+        })
+        .apply(Command::Vote {
+            targets: vec![seat(1), seat(2), seat(3)],
+        })
+        .advance()
+        .expect_actor(6)
+        // The rest of the table votes for the last nominee
+        .advance()
+        .expect_phase(Activity::Evening(FinalSpeech))
+        .expect_alive(&[6], false)
+        .advance_to(night(MafiaShooting))
+        .each_actor(|_| Some(Command::Shoot { target: seat(4) }))
+        .expect_phase(night(SheriffCheck))
+        .advance_to(Activity::Morning(MorningActivity::DeathSpeech))
+        .expect_event(Event::Game(game::Event::MafiaKill { position: seat(4) }))
+        .advance()
+        .expect_actor(4)
+        .expect_alive(&[4, 6], false)
+        .expect_alive(&[1, 2, 3, 5], true);
+}
+
+#[test]
+fn jester_wins_only_when_voted_out() {
+    use EveningActivity::*;
+    use NightActivity::*;
+
+    let mut roles = CLASSIC.to_vec();
+    roles.push((6, Role::Jester));
+
+    // Shot at night, the Jester is just another loss for the town
+    let mut game = Scenario::dealt(&roles);
+    game.advance_to(Activity::Noon(NoonActivity::Discussion))
+        .advance()
+        .apply(Command::Nominate { target: seat(6) })
+        .advance()
+        .apply(Command::Nominate { target: seat(5) })
+        .advance_to(Activity::Evening(Voting))
+        .advance()
+        .expect_actor(6)
+        .apply(Command::Vote {
+            targets: vec![seat(1), seat(2), seat(3)],
+        })
+        .advance()
+        .advance()
+        .expect_alive(&[5], false)
+        .advance_to(night(MafiaShooting))
+        .each_actor(|_| Some(Command::Shoot { target: seat(6) }))
+        .advance_to(Activity::Morning(MorningActivity::DeathSpeech))
+        .expect_alive(&[6], false);
+    assert_eq!(game.engine.result, None);
+
+    // Voted out, the Jester takes the game alone
+    let mut game = Scenario::dealt(&roles);
+    game.advance_to(Activity::Noon(NoonActivity::Discussion))
+        .advance()
+        .apply(Command::Nominate { target: seat(5) })
+        .advance()
+        .apply(Command::Nominate { target: seat(6) })
+        .advance_to(Activity::Evening(Voting))
+        .advance()
+        .expect_actor(5)
+        .apply(Command::Vote {
+            targets: vec![seat(1), seat(2), seat(3)],
+        })
+        .advance()
+        .expect_actor(6)
+        .advance()
+        .expect_alive(&[6], false);
+    let result = game.engine.result.expect("the vote should decide the game");
+    assert_eq!(result.winner, Team::Jester);
+    assert_eq!(result.reason, WinReason::JesterVotedOut);
+}
+
+#[test]
+fn blocked_don_neither_shoots_nor_checks() {
+    use NightActivity::*;
+
+    let mut roles = CLASSIC.to_vec();
+    roles.extend([(5, Role::Prostitute), (6, Role::Doctor)]);
+    let mut game = Scenario::dealt(&roles);
+    game.advance_to(Activity::Noon(NoonActivity::Discussion))
+        .advance_to(night(ProstituteBlock))
+        .advance()
+        .expect_actor(5)
+        .apply(Command::Block { target: seat(1) })
+        .advance_to(night(MafiaShooting))
+        .each_actor(|_| Some(Command::Shoot { target: seat(7) }))
+        .expect_no_event(|e| *e == Event::ActorAdvanced { to: seat(1) })
+        .expect_phase(night(DoctorHeal))
+        .advance()
+        .apply(Command::Heal { target: seat(7) })
+        .advance_to(night(SheriffCheck))
+        .advance()
+        .apply(Command::Check { target: seat(1) })
+        .advance_to(Activity::Noon(NoonActivity::Discussion))
+        .expect_event(Event::Game(game::Event::Night(game::night::Event::Saved {
+            position: seat(7),
+        })))
+        .expect_no_event(|e| {
+            matches!(
+                e,
+                Event::PhaseAdvanced {
+                    to: Activity::Night(DonCheck),
+                    ..
+                }
+            )
+        })
+        .expect_alive(&[7], true);
+}
+
+#[test]
+fn random_commands_never_break_the_game() {
+    let casual = [
+        (1, Role::Don),
+        (2, Role::Mafia),
+        (4, Role::Sheriff),
+        (5, Role::Doctor),
+        (6, Role::Prostitute),
+        (7, Role::Maniac),
+        (8, Role::Jester),
+    ];
+    for seed in 0..200 {
+        let roles: &[(u8, Role)] = if seed % 2 == 0 { &CLASSIC } else { &casual };
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| fuzz_game(seed, roles)));
+        if let Err(err) = outcome {
+            let sent = fuzz_game_commands(seed);
+            panic::resume_unwind(Box::new(format!(
+                "seed {seed} broke the game: {}\ncommands: {sent:?}",
+                err.downcast_ref::<String>()
+                    .map(String::as_str)
+                    .or_else(|| err.downcast_ref::<&str>().copied())
+                    .unwrap_or("panic")
+            )));
+        }
+    }
+}
+
+/// Commands of the fuzzed game of `seed`, drawn again without playing them.
+fn fuzz_game_commands(seed: u64) -> Vec<Command> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..FUZZ_STEPS).map(|_| random_command(&mut rng)).collect()
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::{Activity, EveningActivity, MorningActivity, NoonActivity, Position};
+use crate::snapshot::{self, Restore, Snapshot};
+
+/// Clock of the current actor's turn.
+///
+/// The engine only records what happened to the clock; the countdown itself
+/// runs in the app, which reports back when the time is up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timer {
+    speaker: Position,
+    /// Limit of the turn, extensions included.
+    seconds: u64,
+    /// Remaining seconds while paused.
+    paused: Option<u64>,
+    /// Overtime penalties only apply to speeches.
+    speech: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Event {
+    Started { speaker: Position, seconds: u64 },
+    Paused { remaining: u64 },
+    Resumed { remaining: u64 },
+    Extended { seconds: u64 },
+    Expired { speaker: Position },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Started { speaker, seconds } => {
+                write!(f, "Timer started for {speaker}: {seconds}s")
+            }
+            Event::Paused { remaining } => write!(f, "Timer paused with {remaining}s left"),
+            Event::Resumed { remaining } => write!(f, "Timer resumed with {remaining}s left"),
+            Event::Extended { seconds } => write!(f, "Timer extended by {seconds}s"),
+            Event::Expired { speaker } => write!(f, "Time is up for {speaker}"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No timer is running")]
+    NotRunning,
+
+    #[error("Timer is already paused")]
+    AlreadyPaused,
+
+    #[error("Timer is not paused")]
+    NotPaused,
+
+    #[error("Timer is paused")]
+    Paused,
+}
+
+impl Snapshot for Timer {
+    type Output = snapshot::Timer;
+
+    fn snapshot(&self) -> Self::Output {
+        snapshot::Timer {
+            speaker: self.speaker,
+            seconds: self.seconds,
+            paused: self.paused,
+            speech: self.speech,
+        }
+    }
+}
+
+impl Restore for Timer {
+    type Input = snapshot::Timer;
+    type Error = std::convert::Infallible;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error> {
+        Ok(Self {
+            speaker: snapshot.speaker,
+            seconds: snapshot.seconds,
+            paused: snapshot.paused,
+            speech: snapshot.speech,
+        })
+    }
+}
+
+/// Turns in which the actor speaks to the table.
+pub fn is_speech(activity: Activity) -> bool {
+    matches!(
+        activity,
+        Activity::Morning(MorningActivity::DeathSpeech)
+            | Activity::Noon(NoonActivity::Discussion)
+            | Activity::Evening(EveningActivity::TieDiscussion | EveningActivity::FinalSpeech)
+    )
+}
+
+impl Timer {
+    pub fn start(speaker: Position, seconds: u64, speech: bool) -> (Self, Event) {
+        let timer = Self {
+            speaker,
+            seconds,
+            paused: None,
+            speech,
+        };
+        (timer, Event::Started { speaker, seconds })
+    }
+
+    pub fn speaker(&self) -> Position {
+        self.speaker
+    }
+
+    pub fn seconds(&self) -> u64 {
+        self.seconds
+    }
+
+    pub fn is_speech(&self) -> bool {
+        self.speech
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    pub fn pause(&mut self, remaining: u64) -> Result<Event, Error> {
+        if self.paused.is_some() {
+            return Err(Error::AlreadyPaused);
+        }
+        let remaining = remaining.min(self.seconds);
+        self.paused = Some(remaining);
+        Ok(Event::Paused { remaining })
+    }
+
+    pub fn resume(&mut self) -> Result<Event, Error> {
+        let remaining = self.paused.take().ok_or(Error::NotPaused)?;
+        Ok(Event::Resumed { remaining })
+    }
+
+    pub fn extend(&mut self, seconds: u64) -> Event {
+        self.seconds += seconds;
+        if let Some(remaining) = &mut self.paused {
+            *remaining += seconds;
+        }
+        Event::Extended { seconds }
+    }
+
+    /// A paused clock cannot run out.
+    pub fn expire(&self) -> Result<Event, Error> {
+        if self.paused.is_some() {
+            return Err(Error::Paused);
+        }
+        Ok(Event::Expired {
+            speaker: self.speaker,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paused_timer_keeps_extensions_and_cannot_expire() {
+        let (mut timer, _) = Timer::start(Position::new(3), 60, true);
+
+        assert_eq!(timer.pause(25).unwrap(), Event::Paused { remaining: 25 });
+        assert!(matches!(timer.pause(20), Err(Error::AlreadyPaused)));
+        assert!(matches!(timer.expire(), Err(Error::Paused)));
+
+        timer.extend(30);
+        assert_eq!(timer.seconds(), 90);
+        assert_eq!(timer.resume().unwrap(), Event::Resumed { remaining: 55 });
+        assert!(matches!(timer.resume(), Err(Error::NotPaused)));
+        assert!(timer.expire().is_ok());
+    }
+}
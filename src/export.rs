@@ -0,0 +1,558 @@
+use clap::ValueEnum;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::domain::{Position, Role, Status, Team};
+use crate::snapshot;
+
+/// Layout of an exported game protocol.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Readable protocol as a Markdown document
+    #[default]
+    #[value(alias = "md")]
+    Markdown,
+    /// Standalone HTML page
+    Html,
+    /// One row per seat, as on federation protocol sheets
+    Csv,
+}
+
+impl Format {
+    /// Guesses the format from a file extension.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "md" | "markdown" => Some(Format::Markdown),
+            "html" | "htm" => Some(Format::Html),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Renders the protocol of `engine`'s game in `format`.
+pub fn render(engine: &snapshot::Engine, format: Format) -> String {
+    let protocol = Protocol::new(engine);
+    match format {
+        Format::Markdown => protocol.markdown(),
+        Format::Html => protocol.html(),
+        Format::Csv => protocol.csv(),
+    }
+}
+
+/// Everything a protocol shows, gathered once for every layout.
+struct Protocol {
+    seats: Vec<Seat>,
+    days: Vec<DayRecord>,
+    guesses: Vec<(Position, Vec<Position>)>,
+    result: Option<String>,
+}
+
+struct Seat {
+    position: Position,
+    name: String,
+    role: Option<Role>,
+    warnings: u8,
+    status: Status,
+    /// Day the player left the game, if known.
+    out_day: Option<usize>,
+    sheriff_checked: Option<usize>,
+    don_checked: Option<usize>,
+    won: Option<bool>,
+}
+
+#[derive(Default)]
+struct DayRecord {
+    day: usize,
+    kill: Option<Position>,
+    maniac_kill: Option<Position>,
+    sheriff_check: Option<(Position, Option<Role>)>,
+    don_check: Option<(Position, Option<Role>)>,
+    nominations: Vec<(Position, Position)>,
+    votes: Vec<(Position, Vec<Position>)>,
+    tie_votes: Vec<(Position, Vec<Position>)>,
+    final_vote: Option<snapshot::FinalVote>,
+    eliminated: Vec<Position>,
+}
+
+impl DayRecord {
+    fn is_empty(&self) -> bool {
+        self.kill.is_none()
+            && self.maniac_kill.is_none()
+            && self.sheriff_check.is_none()
+            && self.don_check.is_none()
+            && self.nominations.is_empty()
+            && self.eliminated.is_empty()
+    }
+}
+
+impl Protocol {
+    fn new(engine: &snapshot::Engine) -> Self {
+        let game = &engine.game;
+        let role_at = |position: Position| {
+            game.players
+                .iter()
+                .find(|p| p.position == Some(position))
+                .and_then(|p| p.role)
+        };
+        let day_of = |found: &dyn Fn(usize) -> bool| (0..=engine.day).find(|d| found(*d));
+        let maniac_kill = |day: usize| game.night.get(&day).and_then(|n| n.maniac_kill);
+
+        let mut seats: Vec<Seat> = game
+            .players
+            .iter()
+            .filter_map(|p| {
+                let position = p.position?;
+                let out_day = match p.status {
+                    Status::Dead => day_of(&|d| {
+                        game.kill.get(&d) == Some(&position) || maniac_kill(d) == Some(position)
+                    }),
+                    Status::Eliminated => day_of(&|d| {
+                        game.eliminated
+                            .get(&d)
+                            .is_some_and(|e| e.contains(&position))
+                    }),
+                    Status::Alive | Status::Removed => None,
+                };
+                Some(Seat {
+                    position,
+                    name: p.name.clone(),
+                    role: p.role,
+                    warnings: p.warnings,
+                    status: p.status,
+                    out_day,
+                    sheriff_checked: day_of(&|d| {
+                        game.check.get(&d).and_then(|c| c.sheriff) == Some(position)
+                    }),
+                    don_checked: day_of(&|d| {
+                        game.check.get(&d).and_then(|c| c.don) == Some(position)
+                    }),
+                    won: engine
+                        .result
+                        .zip(p.role)
+                        .map(|(result, role)| role.team() == result.winner),
+                })
+            })
+            .collect();
+        seats.sort_by_key(|s| s.position);
+
+        let ordered_votes = |voting: Option<&snapshot::Voting>| {
+            voting.map_or(Vec::new(), |v| {
+                v.nominees
+                    .iter()
+                    .map(|n| {
+                        let mut voters = v.votes.get(n).cloned().unwrap_or_default();
+                        voters.sort();
+                        (*n, voters)
+                    })
+                    .collect()
+            })
+        };
+
+        let days = (0..=engine.day)
+            .map(|day| {
+                let check = game.check.get(&day);
+                let voting = game.voting.get(&day);
+                let mut nominations: Vec<(Position, Position)> = voting
+                    .map(|v| v.nominations.iter().map(|(by, n)| (*by, *n)).collect())
+                    .unwrap_or_default();
+                let order = |nominee: &Position| {
+                    voting.and_then(|v| v.nominees.iter().position(|n| n == nominee))
+                };
+                nominations.sort_by_key(|(by, nominee)| (order(nominee), *by));
+
+                DayRecord {
+                    day,
+                    kill: game.kill.get(&day).copied(),
+                    maniac_kill: maniac_kill(day),
+                    sheriff_check: check.and_then(|c| c.sheriff).map(|p| (p, role_at(p))),
+                    don_check: check.and_then(|c| c.don).map(|p| (p, role_at(p))),
+                    nominations,
+                    votes: ordered_votes(voting),
+                    tie_votes: ordered_votes(game.tie_voting.get(&day)),
+                    final_vote: game.final_vote.get(&day).cloned(),
+                    eliminated: game.eliminated.get(&day).cloned().unwrap_or_default(),
+                }
+            })
+            .filter(|d| !d.is_empty())
+            .collect();
+
+        let mut guesses: Vec<(Position, Vec<Position>)> = game
+            .guesses
+            .iter()
+            .map(|(by, targets)| (*by, targets.clone()))
+            .collect();
+        guesses.sort();
+
+        Self {
+            seats,
+            days,
+            guesses,
+            result: engine.result.map(|r| r.to_string()),
+        }
+    }
+
+    fn name(&self, position: Position) -> String {
+        self.seats
+            .iter()
+            .find(|s| s.position == position)
+            .map_or_else(
+                || position.to_string(),
+                |s| format!("{position} {}", s.name),
+            )
+    }
+
+    fn role(&self, position: Position) -> Option<Role> {
+        self.seats
+            .iter()
+            .find(|s| s.position == position)
+            .and_then(|s| s.role)
+    }
+
+    /// Readable lines of one day, shared by Markdown and HTML.
+    fn day_lines(&self, day: &DayRecord) -> Vec<String> {
+        let list = |positions: &[Position]| {
+            positions
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut lines = Vec::new();
+
+        if let Some(kill) = day.kill {
+            lines.push(format!("Night: mafia killed {}", self.name(kill)));
+        }
+        if let Some(kill) = day.maniac_kill {
+            lines.push(format!("Night: maniac killed {}", self.name(kill)));
+        }
+        if let Some((target, role)) = day.sheriff_check {
+            let team = role.map_or("unknown", |r| {
+                if r.team() == Team::Mafia {
+                    "Mafia"
+                } else {
+                    "Citizen"
+                }
+            });
+            lines.push(format!(
+                "Night: sheriff checked {}: {team}",
+                self.name(target)
+            ));
+        }
+        if let Some((target, role)) = day.don_check {
+            let seen = if role == Some(Role::Sheriff) {
+                "Sheriff"
+            } else {
+                "not the Sheriff"
+            };
+            lines.push(format!("Night: don checked {}: {seen}", self.name(target)));
+        }
+        for (by, nominee) in &day.nominations {
+            lines.push(format!(
+                "{} nominated {}",
+                self.name(*by),
+                self.name(*nominee)
+            ));
+        }
+        for (nominee, voters) in &day.votes {
+            lines.push(format!(
+                "Vote for {}: {} ({})",
+                self.name(*nominee),
+                voters.len(),
+                list(voters)
+            ));
+        }
+        for (nominee, voters) in &day.tie_votes {
+            lines.push(format!(
+                "Revote for {}: {} ({})",
+                self.name(*nominee),
+                voters.len(),
+                list(voters)
+            ));
+        }
+        if let Some(vote) = &day.final_vote {
+            lines.push(format!(
+                "Lift all of {}: {} yes, {} no",
+                list(&vote.candidates),
+                vote.yes.len(),
+                vote.voters.len() - vote.yes.len()
+            ));
+        }
+        if !day.eliminated.is_empty() {
+            let names: Vec<String> = day.eliminated.iter().map(|p| self.name(*p)).collect();
+            lines.push(format!("Voted out: {}", names.join(", ")));
+        }
+        lines
+    }
+
+    fn guess_lines(&self) -> Vec<String> {
+        self.guesses
+            .iter()
+            .map(|(by, targets)| {
+                let hits = targets
+                    .iter()
+                    .filter(|t| self.role(**t).is_some_and(|r| r.team() == Team::Mafia))
+                    .count();
+                let targets: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+                format!(
+                    "{} named {} ({hits} mafia)",
+                    self.name(*by),
+                    targets.join(", ")
+                )
+            })
+            .collect()
+    }
+
+    fn seat_cells(seat: &Seat) -> [String; 6] {
+        let out = match (seat.status, seat.out_day) {
+            (Status::Alive, _) => String::new(),
+            (status, Some(day)) => format!("{status} on day {day}"),
+            (status, None) => status.to_string(),
+        };
+        [
+            seat.position.to_string(),
+            seat.name.clone(),
+            seat.role.map_or_else(String::new, |r| r.to_string()),
+            seat.role.map_or_else(String::new, |r| r.team().to_string()),
+            seat.warnings.to_string(),
+            out,
+        ]
+    }
+
+    fn markdown(&self) -> String {
+        let mut out = String::from("# Game protocol\n\n## Seating\n\n");
+        out.push_str("| Seat | Player | Role | Team | Warnings | Out |\n");
+        out.push_str("|---|---|---|---|---|---|\n");
+        for seat in &self.seats {
+            let cells = Self::seat_cells(seat).map(|c| c.replace('|', "\\|"));
+            let _ = writeln!(out, "| {} |", cells.join(" | "));
+        }
+
+        for day in &self.days {
+            let _ = writeln!(out, "\n## Day {}\n", day.day);
+            for line in self.day_lines(day) {
+                let _ = writeln!(out, "- {line}");
+            }
+        }
+
+        let guesses = self.guess_lines();
+        if !guesses.is_empty() {
+            out.push_str("\n## Best guess\n\n");
+            for line in guesses {
+                let _ = writeln!(out, "- {line}");
+            }
+        }
+
+        if let Some(result) = &self.result {
+            let _ = writeln!(out, "\n## Result\n\n{result}");
+        }
+        out
+    }
+
+    fn html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Game protocol</title>\n<style>\n\
+             body { font-family: sans-serif; max-width: 50em; margin: 2em auto; }\n\
+             table { border-collapse: collapse; }\n\
+             th, td { border: 1px solid #999; padding: 0.2em 0.6em; }\n\
+             </style>\n</head>\n<body>\n<h1>Game protocol</h1>\n<h2>Seating</h2>\n<table>\n\
+             <tr><th>Seat</th><th>Player</th><th>Role</th><th>Team</th><th>Warnings</th>\
+             <th>Out</th></tr>\n",
+        );
+        for seat in &self.seats {
+            out.push_str("<tr>");
+            for cell in Self::seat_cells(seat) {
+                let _ = write!(out, "<td>{}</td>", escape_html(&cell));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+
+        let mut section = |title: String, lines: Vec<String>| {
+            let _ = writeln!(out, "<h2>{}</h2>\n<ul>", escape_html(&title));
+            for line in lines {
+                let _ = writeln!(out, "<li>{}</li>", escape_html(&line));
+            }
+            out.push_str("</ul>\n");
+        };
+        for day in &self.days {
+            section(format!("Day {}", day.day), self.day_lines(day));
+        }
+        let guesses = self.guess_lines();
+        if !guesses.is_empty() {
+            section("Best guess".to_string(), guesses);
+        }
+
+        if let Some(result) = &self.result {
+            let _ = writeln!(out, "<h2>Result</h2>\n<p>{}</p>", escape_html(result));
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn csv(&self) -> String {
+        let mut out = String::from(
+            "seat,player,role,team,warnings,out,out_day,sheriff_check,don_check,best_guess,won\n",
+        );
+        let day = |d: Option<usize>| d.map_or_else(String::new, |d| d.to_string());
+
+        for seat in &self.seats {
+            let out_status = match seat.status {
+                Status::Alive => String::new(),
+                status => status.to_string(),
+            };
+            let guess = self
+                .guesses
+                .iter()
+                .find(|(by, _)| *by == seat.position)
+                .map_or_else(String::new, |(_, targets)| {
+                    targets
+                        .iter()
+                        .map(|t| t.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                });
+            let row = [
+                seat.position.to_string(),
+                seat.name.clone(),
+                seat.role.map_or_else(String::new, |r| r.to_string()),
+                seat.role.map_or_else(String::new, |r| r.team().to_string()),
+                seat.warnings.to_string(),
+                out_status,
+                day(seat.out_day),
+                day(seat.sheriff_checked),
+                day(seat.don_checked),
+                guess,
+                seat.won.map_or_else(String::new, |w| w.to_string()),
+            ];
+            let row: Vec<String> = row.iter().map(|c| escape_csv(c)).collect();
+            let _ = writeln!(out, "{}", row.join(","));
+        }
+        out
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DayIndex;
+    use crate::engine::result::{GameResult, WinReason};
+    use crate::engine::{Engine, rules::RuleSet};
+    use crate::snapshot::Snapshot;
+
+    fn player(name: &str, position: u8, role: Role, status: Status) -> snapshot::Player {
+        snapshot::Player {
+            name: name.to_string(),
+            position: Some(Position::new(position)),
+            role: Some(role),
+            is_silenced: false,
+            shots: None,
+            warnings: 0,
+            status,
+        }
+    }
+
+    fn finished_game() -> snapshot::Engine {
+        let mut engine = Engine::new(RuleSet::default()).snapshot();
+        engine.day = 2;
+        engine.game.players = vec![
+            player("don", 1, Role::Don, Status::Eliminated),
+            player("sheriff", 2, Role::Sheriff, Status::Alive),
+            player("Smith, Jr.", 3, Role::Citizen, Status::Dead),
+        ];
+        engine.game.kill.insert(1, Position::new(3));
+        engine.game.check.insert(
+            1,
+            snapshot::Check {
+                sheriff: Some(Position::new(1)),
+                don: Some(Position::new(2)),
+            },
+        );
+        engine.game.voting.insert(
+            1,
+            snapshot::Voting {
+                nominations: [(Position::new(2), Position::new(1))].into(),
+                nominees: vec![Position::new(1)],
+                votes: [(Position::new(1), vec![Position::new(2), Position::new(1)])].into(),
+                ..snapshot::Voting::default()
+            },
+        );
+        engine.game.eliminated.insert(1, vec![Position::new(1)]);
+        engine.result = Some(GameResult {
+            winner: Team::Town,
+            reason: WinReason::MafiaEliminated,
+            day: DayIndex::new(1),
+        });
+        engine
+    }
+
+    #[test]
+    fn markdown_lists_every_day_of_the_game() {
+        let markdown = render(&finished_game(), Format::Markdown);
+
+        assert!(markdown.contains("| 1 | don | Don | Mafia | 0 | eliminated on day 1 |"));
+        assert!(markdown.contains("- Night: mafia killed 3 Smith, Jr."));
+        assert!(markdown.contains("- Night: sheriff checked 1 don: Mafia"));
+        assert!(markdown.contains("- Vote for 1 don: 2 (1, 2)"));
+        assert!(markdown.contains("## Result\n\nTown wins on day 1"));
+    }
+
+    #[test]
+    fn csv_has_one_quoted_row_per_seat() {
+        let csv = render(&finished_game(), Format::Csv);
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1], "1,don,Don,Mafia,0,eliminated,1,1,,,false");
+        assert_eq!(rows[3], "3,\"Smith, Jr.\",Citizen,Town,0,dead,1,,,,true");
+    }
+
+    #[test]
+    fn third_party_win_marks_only_its_team() {
+        let mut engine = finished_game();
+        engine.game.players[0].role = Some(Role::Jester);
+        engine.result = Some(GameResult {
+            winner: Team::Jester,
+            reason: WinReason::JesterVotedOut,
+            day: DayIndex::new(1),
+        });
+
+        let csv = render(&engine, Format::Csv);
+        let won: Vec<&str> = csv
+            .lines()
+            .skip(1)
+            .map(|r| r.rsplit(',').next().unwrap())
+            .collect();
+
+        assert!(csv.contains("1,don,Jester,Jester,"));
+        assert_eq!(won, vec!["true", "false", "false"]);
+        assert!(render(&engine, Format::Markdown).contains("the jester was voted out"));
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        let mut engine = finished_game();
+        engine.game.players[1].name = "<b>".to_string();
+
+        let html = render(&engine, Format::Html);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td>&lt;b&gt;</td>"));
+    }
+}
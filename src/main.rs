@@ -1,24 +1,168 @@
 mod app;
 mod domain;
 mod engine;
+mod export;
+mod net;
+mod scoring;
+mod script;
 mod snapshot;
+mod stats;
 mod storage;
 mod tui;
 
 use app::{App, AppStatus, events::Event as AppEvent};
+use domain::Position;
+use engine::{Engine, commands::Command as EngineCommand, rules::RuleSet};
+use scoring::ScoringTable;
 use snapshot::Snapshot;
 
-use ratatui::crossterm::event::{self, Event};
+use clap::{Parser, Subcommand};
+use ratatui::crossterm::event::{self, Event, KeyCode};
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+/// Terminal host for a Mafia game
+#[derive(Debug, Parser)]
+struct Cli {
+    /// Resume a game previously saved as JSON
+    #[arg(long, value_name = "FILE")]
+    resume: Option<PathBuf>,
+
+    /// Play under the rule set from a JSON file instead of the classic rules
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+
+    /// Score games with the points table from a JSON file
+    #[arg(long, value_name = "FILE")]
+    scoring: Option<PathBuf>,
+
+    /// Leaderboard season finished games count towards (default: current year)
+    #[arg(long)]
+    season: Option<String>,
+
+    /// Stream the public table to display clients connecting on this address
+    #[arg(long, value_name = "ADDR")]
+    serve: Option<String>,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Mode {
+    /// Run a file of host commands through the engine without a terminal
+    Run {
+        script: PathBuf,
+
+        #[arg(long, value_enum, default_value_t)]
+        format: script::Format,
+
+        /// Seed seat and role draws for a reproducible run
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Print the protocol of a saved game
+    Export {
+        save: PathBuf,
+
+        #[arg(long, value_enum, default_value_t)]
+        format: export::Format,
+    },
+    /// Show the public table streamed by a host started with `--serve`
+    Watch {
+        /// Host address, e.g. 192.168.1.10:7070
+        addr: String,
+    },
+    /// Private device for one seat: shows its own role and sends its night
+    /// actions to a host started with `--serve`
+    Seat {
+        /// Host address, e.g. 192.168.1.10:7070
+        addr: String,
+        seat: u8,
+        /// PIN the host issued for this seat
+        pin: u16,
+    },
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tui::install_panic_hook();
-    let mut terminal = tui::init_terminal()?;
+    let cli = Cli::parse();
 
     let (event_tx, mut event_rx) = mpsc::channel::<AppEvent>(32);
-    let mut app = App::new(event_tx.clone());
+    let rules = match &cli.rules {
+        Some(path) => RuleSet::from_file(path)?,
+        None => RuleSet::default(),
+    };
+
+    if let Some(Mode::Run {
+        script,
+        format,
+        seed,
+    }) = &cli.mode
+    {
+        let mut engine = match (&cli.resume, seed) {
+            (Some(path), _) => storage::load_engine(path)?,
+            (None, Some(seed)) => Engine::with_seed(rules, *seed),
+            (None, None) => Engine::new(rules),
+        };
+        let commands = std::fs::read_to_string(script)?;
+        return script::run(
+            &mut engine,
+            &commands,
+            *format,
+            &mut std::io::stdout().lock(),
+        );
+    }
+    if let Some(Mode::Export { save, format }) = &cli.mode {
+        let engine = storage::load_engine(save)?;
+        print!("{}", export::render(&engine.snapshot(), *format));
+        return Ok(());
+    }
+    if let Some(Mode::Watch { addr }) = &cli.mode {
+        return watch(addr.clone(), None, rules).await;
+    }
+    if let Some(Mode::Seat { addr, seat, pin }) = &cli.mode {
+        return watch(addr.clone(), Some((Position::new(*seat), *pin)), rules).await;
+    }
+
+    let scoring = match &cli.scoring {
+        Some(path) => ScoringTable::from_file(path)?,
+        None => ScoringTable::default(),
+    };
+    let season = cli
+        .season
+        .unwrap_or_else(|| chrono::Local::now().format("%Y").to_string());
+    let mut app = App::new(event_tx.clone(), rules, scoring, season);
+
+    if let Some(path) = &cli.resume {
+        app.load_from_file(path)?;
+        app.events
+            .push(AppEvent::Loaded(path.display().to_string()));
+    }
+
+    let server = match &cli.serve {
+        Some(addr) => {
+            let (action_tx, mut action_rx) = mpsc::channel::<net::SeatAction>(8);
+            let seat_tx = event_tx.clone();
+            tokio::spawn(async move {
+                while let Some(net::SeatAction { seat, command }) = action_rx.recv().await {
+                    let _ = seat_tx.send(AppEvent::SeatAction { seat, command }).await;
+                }
+            });
+            let (server, _) = net::Server::bind(addr.as_str(), action_tx).await?;
+            for seat in (1..=RuleSet::MAX_PLAYERS).map(Position::new) {
+                if let Some(pin) = server.pin(seat) {
+                    app.events.push(AppEvent::SeatPin { seat, pin });
+                }
+            }
+            Some(server)
+        }
+        None => None,
+    };
+
+    tui::install_panic_hook();
+    let mut terminal = tui::init_terminal()?;
 
     // Input task
     let input_tx = event_tx.clone();
@@ -40,26 +184,40 @@ async fn main() -> anyhow::Result<()> {
     while app.status == AppStatus::Running {
         tokio::select! {
                     Some(event) = event_rx.recv() => {
+                        app.record_app_event(&event);
                         match event {
                             AppEvent::QuitRequested => app.status = AppStatus::Quit,
                             AppEvent::Key(key) => {
             app.handle_key(key).await;
         }
                             AppEvent::Engine(event) => {
+                                if let Some(server) = &server {
+                                    server.publish_event(&event);
+                                }
                                 app.events.push(AppEvent::Engine(event));
                                 if app.events.len() > 100 {
                                     app.events.remove(0);
                                 }
                             }
+                            AppEvent::Reverted(event) => app.events.push(AppEvent::Reverted(event)),
                             AppEvent::End => {},
+                            AppEvent::Loaded(path) => app.events.push(AppEvent::Loaded(path)),
+                            AppEvent::Exported(path) => app.events.push(AppEvent::Exported(path)),
                             AppEvent::TimerStarted(s) => app.current_timer = Some(s),
                             AppEvent::TimerTick(s) => app.current_timer = Some(s),
                             AppEvent::TimerEnded => app.current_timer = None,
+                            AppEvent::TurnTimeUp(generation) => app.turn_time_up(generation).await,
                             AppEvent::Error(_) => {}, // handle if needed
+                            AppEvent::SeatAction { seat, command } => app.receive_seat_action(seat, command),
+                            AppEvent::SeatPin { .. } => {}
                         }
                     }
                     _ = tick_interval.tick() => {
-                        terminal.draw(|f| tui::draw(f, &app.snapshot())).unwrap();
+                        let snapshot = app.snapshot();
+                        if let Some(server) = &server {
+                            server.publish_state(net::TableState::from_app(&snapshot));
+                        }
+                        terminal.draw(|f| tui::draw(f, &snapshot)).unwrap();
                     }
                 }
     }
@@ -67,3 +225,109 @@ async fn main() -> anyhow::Result<()> {
     tui::restore_terminal()?;
     Ok(())
 }
+
+/// Client screen mirroring the table a host streams. Without a seat it is
+/// a read-only display; a seat's device also sends its night actions.
+async fn watch(addr: String, seat: Option<(Position, u16)>, rules: RuleSet) -> anyhow::Result<()> {
+    let (message_tx, mut message_rx) = mpsc::channel(32);
+    let (command_tx, command_rx) = mpsc::channel(8);
+    let connection = tokio::spawn(net::connect(addr.clone(), seat, message_tx, command_rx));
+
+    let (key_tx, mut key_rx) = mpsc::channel(8);
+    tokio::spawn(async move {
+        loop {
+            if event::poll(Duration::from_millis(50)).unwrap()
+                && let Event::Key(key) = event::read().unwrap()
+            {
+                let _ = key_tx.send(key).await;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    });
+
+    let mut table = snapshot::App {
+        engine: Engine::new(rules).snapshot(),
+        input: String::new(),
+        input_mode: app::input::InputMode::Normal,
+        events: Vec::new(),
+        current_timer: None,
+        stats: None,
+        final_scores: None,
+        allowed: Vec::new(),
+    };
+    let mut connection_status = format!("Connecting to {addr}…");
+    let mut rejected = false;
+    let mut notice = String::from("Type shoot <seat>, check <seat> or guess <seats>");
+
+    tui::install_panic_hook();
+    let mut terminal = tui::init_terminal()?;
+
+    let tick_interval = tokio::time::interval(Duration::from_millis(50));
+    tokio::pin!(tick_interval);
+    loop {
+        tokio::select! {
+            message = message_rx.recv() => match message {
+                Some(net::Message::State(state)) => {
+                    let net::TableState { engine, timer } = *state;
+                    table.engine = engine;
+                    table.current_timer = timer;
+                    connection_status = format!("Watching {addr}");
+                }
+                Some(net::Message::Event(event)) => {
+                    table.events.push(AppEvent::Engine(event));
+                    if table.events.len() > 100 {
+                        table.events.remove(0);
+                    }
+                }
+                Some(net::Message::Rejected(reason)) => {
+                    connection_status = format!("Rejected by {addr}: {reason}");
+                    rejected = true;
+                }
+                None if !rejected => connection_status = format!("Disconnected from {addr}"),
+                None => {}
+            },
+            Some(key) = key_rx.recv() => match (seat, key.code) {
+                (_, KeyCode::Esc) | (None, KeyCode::Char('q')) => break,
+                (Some(_), KeyCode::Enter) => {
+                    notice = match seat_command(&table.input) {
+                        Some(command) => {
+                            let _ = command_tx.send(command).await;
+                            format!("Sent `{}`, waiting for the host", table.input.trim())
+                        }
+                        None => "Only shoot <seat>, check <seat> or guess <seats>".to_string(),
+                    };
+                    table.input.clear();
+                }
+                (Some(_), KeyCode::Backspace) => {
+                    table.input.pop();
+                }
+                (Some(_), KeyCode::Char(c)) => table.input.push(c),
+                _ => {}
+            },
+            _ = tick_interval.tick() => {
+                let status = match seat {
+                    Some((seat, _)) => format!("{seat} > {} · {notice} · {connection_status} · Esc to quit", table.input),
+                    None => format!("{connection_status} · q to quit"),
+                };
+                terminal.draw(|f| tui::draw_display(f, &table, &status)).unwrap();
+            }
+        }
+    }
+
+    connection.abort();
+    tui::restore_terminal()?;
+    Ok(())
+}
+
+/// Parses what a seat typed into one of the actions it may send.
+fn seat_command(input: &str) -> Option<EngineCommand> {
+    let command = app::parser::parse_input(input.trim()).ok()?;
+    match EngineCommand::try_from(command).ok()? {
+        command @ (EngineCommand::Shoot { .. }
+        | EngineCommand::Check { .. }
+        | EngineCommand::Heal { .. }
+        | EngineCommand::Block { .. }
+        | EngineCommand::Guess { .. }) => Some(command),
+        _ => None,
+    }
+}
@@ -0,0 +1,473 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, mpsc, watch};
+
+use crate::domain::Position;
+use crate::engine::{Event, commands::Command, rules::RuleSet};
+use crate::snapshot::{self, Visibility};
+
+/// What the host streams to its clients, one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    State(Box<TableState>),
+    Event(Event),
+    /// The seat claim was refused; the host closes the connection after it.
+    Rejected(String),
+}
+
+/// What a client may send to the host, one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Request {
+    /// Claims a seat with the PIN the host issued for it: the connection
+    /// becomes that seat's private device.
+    Seat { seat: Position, pin: u16 },
+    /// A night action, validated and confirmed by the host.
+    Action(Command),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClaimError {
+    #[error("Wrong PIN for seat {0}")]
+    WrongPin(Position),
+    #[error("Seat {0} is already connected")]
+    AlreadyConnected(Position),
+}
+
+/// Seat PINs issued by the host and the seats currently claimed with them.
+#[derive(Debug)]
+struct Seats {
+    pins: HashMap<Position, u16>,
+    claimed: Mutex<HashSet<Position>>,
+}
+
+impl Seats {
+    /// PINs for every seat a table can have, so seats added by resizing
+    /// the lobby can claim a device too.
+    fn issue() -> Self {
+        let mut rng = rand::rng();
+        Self {
+            pins: (1..=RuleSet::MAX_PLAYERS)
+                .map(|seat| (Position::new(seat), rng.random_range(1000..=9999)))
+                .collect(),
+            claimed: Mutex::default(),
+        }
+    }
+
+    fn claim(self: &Arc<Self>, seat: Position, pin: u16) -> Result<Claim, ClaimError> {
+        if self.pins.get(&seat) != Some(&pin) {
+            return Err(ClaimError::WrongPin(seat));
+        }
+        if !self.claimed.lock().unwrap().insert(seat) {
+            return Err(ClaimError::AlreadyConnected(seat));
+        }
+        Ok(Claim {
+            seat,
+            seats: Arc::clone(self),
+        })
+    }
+}
+
+/// A claimed seat, released when its connection ends.
+struct Claim {
+    seat: Position,
+    seats: Arc<Seats>,
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        self.seats.claimed.lock().unwrap().remove(&self.seat);
+    }
+}
+
+/// A night action sent from a seat's device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeatAction {
+    pub seat: Position,
+    pub command: Command,
+}
+
+/// Table state as the host sees it; every client gets its own redaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableState {
+    pub engine: snapshot::Engine,
+    pub timer: Option<u64>,
+}
+
+impl TableState {
+    pub fn from_app(app: &snapshot::App) -> Self {
+        Self {
+            engine: app.engine.clone(),
+            timer: app.current_timer,
+        }
+    }
+
+    pub fn redacted(&self, visibility: Visibility) -> Self {
+        Self {
+            engine: visibility.redact(&self.engine),
+            timer: self.timer,
+        }
+    }
+}
+
+/// Host side: accepts display and seat clients, streams each of them what
+/// it may see and forwards seat actions to the host.
+pub struct Server {
+    state: watch::Sender<Option<TableState>>,
+    events: broadcast::Sender<Event>,
+    seats: Arc<Seats>,
+}
+
+impl Server {
+    /// Starts listening on `addr` and returns the bound address. Every
+    /// seat gets a PIN to claim it with; actions sent from seats are
+    /// delivered to `actions`.
+    pub async fn bind<A: ToSocketAddrs>(
+        addr: A,
+        actions: mpsc::Sender<SeatAction>,
+    ) -> anyhow::Result<(Self, SocketAddr)> {
+        let listener = TcpListener::bind(addr).await?;
+        let local = listener.local_addr()?;
+
+        let (state, _) = watch::channel(None);
+        let (events, _) = broadcast::channel(64);
+        let seats = Arc::new(Seats::issue());
+
+        let accept_state = state.clone();
+        let accept_events = events.clone();
+        let accept_seats = Arc::clone(&seats);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_client(
+                    stream,
+                    accept_state.subscribe(),
+                    accept_events.subscribe(),
+                    Arc::clone(&accept_seats),
+                    actions.clone(),
+                ));
+            }
+        });
+
+        Ok((
+            Self {
+                state,
+                events,
+                seats,
+            },
+            local,
+        ))
+    }
+
+    /// The PIN a seat's device must send to claim `seat`.
+    pub fn pin(&self, seat: Position) -> Option<u16> {
+        self.seats.pins.get(&seat).copied()
+    }
+
+    /// Publishes the current state; clients only hear about changes.
+    pub fn publish_state(&self, state: TableState) {
+        self.state.send_if_modified(|current| {
+            if current.as_ref() == Some(&state) {
+                false
+            } else {
+                *current = Some(state);
+                true
+            }
+        });
+    }
+
+    /// Forwards `event` to every client allowed to see it.
+    pub fn publish_event(&self, event: &Event) {
+        // No receivers just means no client is attached
+        let _ = self.events.send(event.clone());
+    }
+}
+
+async fn serve_client(
+    stream: TcpStream,
+    mut state: watch::Receiver<Option<TableState>>,
+    mut events: broadcast::Receiver<Event>,
+    seats: Arc<Seats>,
+    actions: mpsc::Sender<SeatAction>,
+) -> anyhow::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut requests = BufReader::new(reader).lines();
+    let mut visibility = Visibility::Public;
+    let mut claim = None;
+
+    send_state(&mut writer, &mut state, visibility).await?;
+
+    loop {
+        tokio::select! {
+            changed = state.changed() => {
+                changed?;
+                send_state(&mut writer, &mut state, visibility).await?;
+            }
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let visible = state
+                        .borrow()
+                        .as_ref()
+                        .is_some_and(|s| visibility.can_see(&s.engine, &event));
+                    if visible {
+                        send(&mut writer, &Message::Event(event)).await?;
+                    }
+                }
+                // A slow client misses events but still gets the next state
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(err) => return Err(err.into()),
+            },
+            line = requests.next_line() => {
+                let Some(line) = line? else {
+                    return Ok(());
+                };
+                match serde_json::from_str(&line)? {
+                    Request::Seat { seat, pin } => match seats.claim(seat, pin) {
+                        Ok(claimed) => {
+                            claim = Some(claimed);
+                            visibility = Visibility::Player(seat);
+                            state.mark_changed();
+                        }
+                        Err(err) => {
+                            send(&mut writer, &Message::Rejected(err.to_string())).await?;
+                            return Ok(());
+                        }
+                    },
+                    Request::Action(command) => {
+                        // Anonymous displays cannot act
+                        if let Some(Claim { seat, .. }) = &claim {
+                            actions.send(SeatAction { seat: *seat, command }).await?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn send_state(
+    writer: &mut OwnedWriteHalf,
+    state: &mut watch::Receiver<Option<TableState>>,
+    visibility: Visibility,
+) -> anyhow::Result<()> {
+    let current = state
+        .borrow_and_update()
+        .as_ref()
+        .map(|s| s.redacted(visibility));
+    if let Some(current) = current {
+        send(writer, &Message::State(Box::new(current))).await?;
+    }
+    Ok(())
+}
+
+async fn send<W, T>(writer: &mut W, message: &T) -> anyhow::Result<()>
+where
+    W: AsyncWriteExt + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+    Ok(())
+}
+
+/// Client side: connects to a host and forwards its messages until the
+/// connection closes.
+///
+/// With a `seat` and its PIN, the connection is that seat's private device
+/// and every command received on `commands` is sent to the host as its
+/// action.
+pub async fn connect<A: ToSocketAddrs>(
+    addr: A,
+    seat: Option<(Position, u16)>,
+    tx: mpsc::Sender<Message>,
+    mut commands: mpsc::Receiver<Command>,
+) -> anyhow::Result<()> {
+    let (reader, mut writer) = TcpStream::connect(addr).await?.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    if let Some((seat, pin)) = seat {
+        send(&mut writer, &Request::Seat { seat, pin }).await?;
+    }
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let Some(line) = line? else {
+                    break;
+                };
+                let message: Message = serde_json::from_str(&line)?;
+                if tx.send(message).await.is_err() {
+                    break;
+                }
+            }
+            Some(command) = commands.recv() => {
+                send(&mut writer, &Request::Action(command)).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Role;
+    use crate::engine::{Engine, game, rules::RuleSet};
+    use crate::snapshot::Snapshot;
+
+    fn started_engine() -> Engine {
+        let mut engine = Engine::with_seed(RuleSet::default(), 7);
+        for name in ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"] {
+            engine
+                .apply(Command::Join {
+                    name: name.to_string(),
+                    seat: None,
+                })
+                .unwrap();
+        }
+        engine.apply(Command::Start).unwrap();
+        engine.apply(Command::Advance).unwrap();
+        engine
+    }
+
+    #[tokio::test]
+    async fn display_receives_state_then_public_events() {
+        let (actions, _) = mpsc::channel(8);
+        let (server, addr) = Server::bind("127.0.0.1:0", actions).await.unwrap();
+        server.publish_state(TableState {
+            engine: started_engine().snapshot(),
+            timer: None,
+        });
+
+        let (tx, mut rx) = mpsc::channel(8);
+        let (_commands, commands_rx) = mpsc::channel(8);
+        tokio::spawn(connect(addr, None, tx, commands_rx));
+
+        let Some(Message::State(state)) = rx.recv().await else {
+            panic!("expected the current state first");
+        };
+        assert!(state.engine.game.players.iter().all(|p| p.role.is_none()));
+
+        let secret = Event::Game(game::Event::Player(game::player::Event::RoleAssigned {
+            name: "a".to_string(),
+            position: Position::new(1),
+            role: Role::Don,
+        }));
+        let public = Event::GameStarted;
+        server.publish_event(&secret);
+        server.publish_event(&public);
+
+        assert_eq!(rx.recv().await, Some(Message::Event(public)));
+    }
+
+    #[tokio::test]
+    async fn seat_sees_its_own_role_and_sends_actions() {
+        let (actions, mut actions_rx) = mpsc::channel(8);
+        let (server, addr) = Server::bind("127.0.0.1:0", actions).await.unwrap();
+        let engine = started_engine().snapshot();
+        let seat = engine
+            .game
+            .players
+            .iter()
+            .find(|p| p.role.is_some())
+            .and_then(|p| p.position)
+            .unwrap();
+        server.publish_state(TableState {
+            engine,
+            timer: None,
+        });
+
+        let (tx, mut rx) = mpsc::channel(8);
+        let (commands, commands_rx) = mpsc::channel(8);
+        let pin = server.pin(seat).unwrap();
+        tokio::spawn(connect(addr, Some((seat, pin)), tx, commands_rx));
+
+        // The public state is sent before the seat is claimed
+        loop {
+            let Some(Message::State(state)) = rx.recv().await else {
+                panic!("expected state");
+            };
+            let own = state
+                .engine
+                .game
+                .players
+                .iter()
+                .find(|p| p.position == Some(seat));
+            if own.is_some_and(|p| p.role.is_some()) {
+                break;
+            }
+        }
+
+        let shoot = Command::Shoot {
+            target: Position::new(2),
+        };
+        commands.send(shoot.clone()).await.unwrap();
+        assert_eq!(
+            actions_rx.recv().await,
+            Some(SeatAction {
+                seat,
+                command: shoot
+            })
+        );
+    }
+
+    /// Waits past the public state for the host's answer to a seat claim.
+    async fn claim_answer(rx: &mut mpsc::Receiver<Message>) -> Message {
+        loop {
+            match rx.recv().await {
+                Some(Message::State(state))
+                    if state.engine.game.players.iter().all(|p| p.role.is_none()) => {}
+                Some(message) => return message,
+                None => panic!("connection closed without an answer"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn seat_claims_need_the_pin_and_a_free_seat() {
+        let (actions, _) = mpsc::channel(8);
+        let (server, addr) = Server::bind("127.0.0.1:0", actions).await.unwrap();
+        // Seats a resized lobby may add later have PINs too
+        assert!(server.pin(Position::new(RuleSet::MAX_PLAYERS)).is_some());
+        let engine = started_engine().snapshot();
+        let seat = engine
+            .game
+            .players
+            .iter()
+            .find(|p| p.role.is_some())
+            .and_then(|p| p.position)
+            .unwrap();
+        server.publish_state(TableState {
+            engine,
+            timer: None,
+        });
+        let pin = server.pin(seat).unwrap();
+
+        let (tx, mut rx) = mpsc::channel(8);
+        let (_wrong, commands_rx) = mpsc::channel(8);
+        tokio::spawn(connect(addr, Some((seat, pin % 9999 + 1)), tx, commands_rx));
+        assert_eq!(
+            claim_answer(&mut rx).await,
+            Message::Rejected(format!("Wrong PIN for seat {seat}"))
+        );
+
+        let (tx, mut owner) = mpsc::channel(8);
+        let (_commands, commands_rx) = mpsc::channel(8);
+        tokio::spawn(connect(addr, Some((seat, pin)), tx, commands_rx));
+        assert!(matches!(claim_answer(&mut owner).await, Message::State(_)));
+
+        let (tx, mut rx) = mpsc::channel(8);
+        let (_second, commands_rx) = mpsc::channel(8);
+        tokio::spawn(connect(addr, Some((seat, pin)), tx, commands_rx));
+        assert_eq!(
+            claim_answer(&mut rx).await,
+            Message::Rejected(format!("Seat {seat} is already connected"))
+        );
+    }
+}
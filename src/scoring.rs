@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::domain::Role;
+use crate::engine::result::GameResult;
+use crate::snapshot;
+use crate::stats::{GameRecord, PlayerRecord};
+
+/// Points awarded per game.
+///
+/// Defaults follow common federation tables; leagues load their own from a
+/// JSON file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringTable {
+    pub win: f64,
+    /// Extra points for a role whose team won.
+    pub role_bonus: Vec<RoleBonus>,
+    /// Best guess bonus by number of mafia named, indexed by hits.
+    pub best_guess: Vec<f64>,
+    /// Compensation for the first night victim when their team lost.
+    pub first_killed: f64,
+    /// Added (usually negative) when a player is removed for warnings.
+    pub removed: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RoleBonus {
+    pub role: Role,
+    pub points: f64,
+}
+
+impl Default for ScoringTable {
+    fn default() -> Self {
+        Self {
+            win: 1.0,
+            role_bonus: vec![
+                RoleBonus {
+                    role: Role::Sheriff,
+                    points: 0.3,
+                },
+                RoleBonus {
+                    role: Role::Don,
+                    points: 0.3,
+                },
+            ],
+            best_guess: vec![0.0, 0.0, 0.25, 0.5],
+            first_killed: 0.4,
+            removed: -0.7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerScore {
+    pub name: String,
+    pub role: Role,
+    pub won: bool,
+    pub points: f64,
+}
+
+/// Scores of a finished game, as shown on the end screen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinalScores {
+    pub result: GameResult,
+    pub season: String,
+    pub scores: Vec<PlayerScore>,
+    /// Season rank and total of each scored player, this game included.
+    pub season_standings: HashMap<String, SeasonStanding>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SeasonStanding {
+    pub rank: usize,
+    pub points: f64,
+}
+
+impl ScoringTable {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Points for every player of a finished game, in seat order.
+    pub fn score(&self, game: &snapshot::Game, result: &GameResult) -> Vec<PlayerScore> {
+        self.score_record(&GameRecord::new(game, result))
+    }
+
+    pub fn score_record(&self, record: &GameRecord) -> Vec<PlayerScore> {
+        record
+            .players
+            .iter()
+            .map(|player| PlayerScore {
+                name: player.name.clone(),
+                role: player.role,
+                won: player.won,
+                points: self.points(player),
+            })
+            .collect()
+    }
+
+    fn points(&self, player: &PlayerRecord) -> f64 {
+        let mut points = 0.0;
+
+        if player.won {
+            points += self.win;
+            points += self
+                .role_bonus
+                .iter()
+                .filter(|b| b.role == player.role)
+                .map(|b| b.points)
+                .sum::<f64>();
+        }
+        if let Some(hits) = player.guess_hits {
+            points += self.best_guess.get(hits as usize).copied().unwrap_or(0.0);
+        }
+        if player.shot_first_night && !player.won {
+            points += self.first_killed;
+        }
+        if player.removed {
+            points += self.removed;
+        }
+
+        points
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Standing {
+    pub games: u32,
+    pub wins: u32,
+    pub points: f64,
+}
+
+/// Running totals of a season, kept as one JSON file per season.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub season: String,
+    pub players: HashMap<String, Standing>,
+}
+
+impl Leaderboard {
+    /// Reads the season's leaderboard, starting an empty one if missing.
+    pub fn load<P: AsRef<Path>>(path: P, season: &str) -> anyhow::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self {
+                season: season.to_string(),
+                ..Self::default()
+            }),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, scores: &[PlayerScore]) {
+        for score in scores {
+            let standing = self.players.entry(score.name.clone()).or_default();
+            standing.games += 1;
+            standing.wins += score.won as u32;
+            standing.points += score.points;
+        }
+    }
+
+    /// Players ordered by points, best first.
+    pub fn standings(&self) -> Vec<(&str, &Standing)> {
+        let mut standings: Vec<_> = self
+            .players
+            .iter()
+            .map(|(name, standing)| (name.as_str(), standing))
+            .collect();
+        standings.sort_by(|a, b| b.1.points.total_cmp(&a.1.points).then(a.0.cmp(b.0)));
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DayIndex, Position, Status, Team};
+    use crate::engine::{Engine, result::WinReason, rules::RuleSet};
+    use crate::snapshot::Snapshot;
+
+    fn player(name: &str, position: u8, role: Role, status: Status) -> snapshot::Player {
+        snapshot::Player {
+            name: name.to_string(),
+            position: Some(Position::new(position)),
+            role: Some(role),
+            is_silenced: false,
+            shots: None,
+            warnings: 0,
+            status,
+        }
+    }
+
+    /// Mafia reach parity after losing a player to warnings; the first
+    /// night's victim named both mafia in their guess.
+    fn mafia_win() -> (snapshot::Game, GameResult) {
+        let mut engine = Engine::new(RuleSet::default()).snapshot();
+        engine.game.players = vec![
+            player("don", 1, Role::Don, Status::Alive),
+            player("removed", 2, Role::Mafia, Status::Removed),
+            player("victim", 3, Role::Citizen, Status::Dead),
+            player("sheriff", 4, Role::Sheriff, Status::Eliminated),
+            player("citizen", 5, Role::Citizen, Status::Alive),
+        ];
+        engine.game.players[1].warnings = 4;
+        engine.game.kill.insert(1, Position::new(3));
+        engine.game.guesses.insert(
+            Position::new(3),
+            vec![Position::new(1), Position::new(2), Position::new(5)],
+        );
+        let result = GameResult {
+            winner: Team::Mafia,
+            reason: WinReason::MafiaParity,
+            day: DayIndex::new(2),
+        };
+        (engine.game, result)
+    }
+
+    #[test]
+    fn points_follow_the_scoring_table() {
+        let (game, result) = mafia_win();
+
+        // In hundredths, so sums like 1.0 - 0.7 compare exactly
+        let points: Vec<i64> = ScoringTable::default()
+            .score(&game, &result)
+            .iter()
+            .map(|s| (s.points * 100.0).round() as i64)
+            .collect();
+
+        assert_eq!(points, vec![130, 30, 65, 0, 0]);
+    }
+
+    #[test]
+    fn leaderboard_accumulates_and_ranks_by_points() {
+        let mut board = Leaderboard::default();
+        let score = |name: &str, won, points| PlayerScore {
+            name: name.to_string(),
+            role: Role::Citizen,
+            won,
+            points,
+        };
+
+        board.record(&[score("a", true, 1.0), score("b", false, 0.4)]);
+        board.record(&[score("a", false, 0.0), score("b", true, 1.3)]);
+
+        let standings = board.standings();
+        assert_eq!(standings[0].0, "b");
+        assert_eq!(standings[0].1.games, 2);
+        assert_eq!(standings[1].1.wins, 1);
+    }
+}
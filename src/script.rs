@@ -0,0 +1,165 @@
+use anyhow::{Context, bail};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::Write;
+
+use crate::app::{commands::Command as AppCommand, parser::parse_input};
+use crate::engine::{Engine, Event, commands::Command as EngineCommand};
+use crate::storage;
+
+/// How `mafia run` prints the event stream.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One human readable line per event
+    #[default]
+    Text,
+    /// One JSON object per event (JSON Lines)
+    Json,
+}
+
+/// A single printed event, tagged with the script line that produced it.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    line: usize,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    reverted: bool,
+    event: &'a Event,
+}
+
+/// Feeds every line of `script` through the command parser and the engine,
+/// printing the produced events to `out`.
+///
+/// Blank lines and lines starting with `#` are skipped. Stops with an error
+/// on the first line that fails to parse or is rejected by the engine.
+pub fn run<W: Write>(
+    engine: &mut Engine,
+    script: &str,
+    format: Format,
+    out: &mut W,
+) -> anyhow::Result<()> {
+    for (index, line) in script.lines().enumerate() {
+        let line_no = index + 1;
+        let input = line.trim();
+        if input.is_empty() || input.starts_with('#') {
+            continue;
+        }
+
+        let command = parse_input(input)
+            .with_context(|| format!("line {line_no}: invalid command `{input}`"))?;
+
+        let (events, reverted) = match EngineCommand::try_from(command) {
+            Ok(command) => (engine.apply(command), false),
+            Err(AppCommand::Undo) => (
+                engine.undo().map(|mut e| {
+                    e.reverse();
+                    e
+                }),
+                true,
+            ),
+            Err(AppCommand::Redo) => (engine.redo(), false),
+            Err(AppCommand::End { file_name }) => (
+                storage::save_engine(engine, &[], &file_name).map(|_| Vec::new()),
+                false,
+            ),
+            Err(AppCommand::Export { file_name, format }) => (
+                storage::export_engine(engine, &file_name, format).map(|_| Vec::new()),
+                false,
+            ),
+            Err(AppCommand::Load { file_name }) => (
+                storage::load_engine(&file_name).map(|loaded| {
+                    *engine = loaded;
+                    Vec::new()
+                }),
+                false,
+            ),
+            // Scripts run without a clock, so a paused turn has lost no time
+            Err(AppCommand::Timer { .. }) => continue,
+            Err(AppCommand::Pause) => {
+                let remaining = engine.timer().map_or(0, |t| t.seconds());
+                (engine.apply(EngineCommand::PauseTimer { remaining }), false)
+            }
+            Err(AppCommand::Quit) => break,
+            Err(other) => bail!("line {line_no}: {other:?} is not supported in scripts"),
+        };
+        let events = events.with_context(|| format!("line {line_no}: `{input}` rejected"))?;
+
+        for event in &events {
+            match format {
+                Format::Text if reverted => writeln!(out, "{line_no}: Reverted: {event}")?,
+                Format::Text => writeln!(out, "{line_no}: {event}")?,
+                Format::Json => {
+                    let record = Record {
+                        line: line_no,
+                        reverted,
+                        event,
+                    };
+                    writeln!(out, "{}", serde_json::to_string(&record)?)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::rules::RuleSet;
+
+    const LOBBY: &str = "
+        # ten players take their seats
+        join a
+        join b
+        join c
+        join d
+        join e
+        join f
+        join g
+        join h
+        join i
+        join j
+        start
+    ";
+
+    #[test]
+    fn script_drives_the_engine() {
+        let mut engine = Engine::with_seed(RuleSet::default(), 7);
+        let mut out = Vec::new();
+
+        run(&mut engine, LOBBY, Format::Text, &mut out).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(engine.game.players().len(), 10);
+        assert!(out.lines().last().unwrap().ends_with("Game has started"));
+    }
+
+    #[test]
+    fn json_output_is_one_record_per_event() {
+        let mut engine = Engine::with_seed(RuleSet::default(), 7);
+        let mut out = Vec::new();
+
+        run(&mut engine, "join a\nundo", Format::Json, &mut out).unwrap();
+
+        let records: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["line"], 1);
+        assert!(records[0].get("reverted").is_none());
+        assert_eq!(records[1]["reverted"], true);
+    }
+
+    #[test]
+    fn first_rejected_command_stops_the_script() {
+        let mut engine = Engine::with_seed(RuleSet::default(), 7);
+        let mut out = Vec::new();
+
+        let err = run(&mut engine, "join a\nstart\njoin b", Format::Text, &mut out).unwrap_err();
+
+        assert!(err.to_string().starts_with("line 2:"));
+        assert_eq!(engine.game.players().len(), 1);
+    }
+}
@@ -1,14 +1,28 @@
+pub mod visibility;
+
 use crate::domain::{Activity, EngineState, Position, Role, Status};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use visibility::Visibility;
+
 pub trait Snapshot {
     type Output;
 
     fn snapshot(&self) -> Self::Output;
 }
 
-#[derive(Clone, Debug, Serialize)]
+/// Inverse of [`Snapshot`]: rebuilds live state from its snapshot.
+///
+/// Snapshots come from untrusted save files, so restoring is fallible.
+pub trait Restore: Sized {
+    type Input;
+    type Error;
+
+    fn restore(snapshot: &Self::Input) -> Result<Self, Self::Error>;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Player {
     pub name: String,
     pub position: Option<Position>,
@@ -19,38 +33,101 @@ pub struct Player {
     pub status: Status,
 }
 
-#[derive(Clone, Default, Debug, Serialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Check {
     pub sheriff: Option<Position>,
     pub don: Option<Position>,
 }
 
-#[derive(Default, Clone, Debug, Serialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Night {
+    pub blocked: Option<Position>,
+    pub healed: Option<Position>,
+    pub maniac_shot: Option<Position>,
+    pub maniac_kill: Option<Position>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Voting {
     pub nominations: HashMap<Position, Position>,
     pub nominees: Vec<Position>,
     pub votes: HashMap<Position, Vec<Position>>,
+    pub voters: Vec<Position>,
+    pub remaining_voters: Vec<Position>,
+    pub remaining_nominees: Vec<Position>,
+    /// Votes received by each nominee, in nomination order
+    #[serde(default)]
+    pub tally: Vec<(Position, usize)>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FinalVote {
+    pub candidates: Vec<Position>,
+    pub voters: Vec<Position>,
+    pub yes: Vec<Position>,
+    /// Derived from `voters` and `yes`; kept for readers of the save.
+    #[serde(default)]
+    pub no: Vec<Position>,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Game {
     pub players: Vec<Player>,
     pub kill: HashMap<usize, Position>,
     pub voting: HashMap<usize, Voting>,
     pub tie_voting: HashMap<usize, Voting>,
-    pub final_voting: HashMap<usize, Vec<Position>>,
+    #[serde(default)]
+    pub final_vote: HashMap<usize, FinalVote>,
     pub check: HashMap<usize, Check>,
-    pub guess: Vec<Position>,
+    #[serde(default)]
+    pub night: HashMap<usize, Night>,
+    #[serde(default)]
+    pub guesses: HashMap<Position, Vec<Position>>,
     pub eliminated: HashMap<usize, Vec<Position>>,
+    pub rules: crate::engine::rules::RuleSet,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Actor {
+    pub start: Position,
+    pub current: Option<Position>,
+    pub completed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Engine {
     pub game: Game,
     pub phase: Option<Activity>,
     pub day: usize,
     pub state: EngineState,
-    pub actor: Option<Position>,
+    pub actor: Actor,
+    #[serde(default)]
+    pub result: Option<crate::engine::result::GameResult>,
+    #[serde(default)]
+    pub timer: Option<Timer>,
+    pub last_discussion_started: Position,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Timer {
+    pub speaker: Position,
+    pub seconds: u64,
+    pub paused: Option<u64>,
+    pub speech: bool,
+}
+
+/// On-disk save: the engine snapshot plus the journal that produced it and
+/// the event log of the game.
+///
+/// Without a journal the engine is restored from the snapshot alone.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Save {
+    pub engine: Engine,
+    #[serde(default)]
+    pub journal: Option<crate::engine::journal::Journal>,
+    /// Timestamped events of the game, oldest first.
+    #[serde(default)]
+    pub log: Vec<crate::app::log::Entry>,
 }
 
 #[derive(Clone, Debug)]
@@ -60,4 +137,8 @@ pub struct App {
     pub input_mode: crate::app::input::InputMode,
     pub events: Vec<crate::app::events::Event>,
     pub current_timer: Option<u64>,
+    pub stats: Option<crate::stats::PlayerStats>,
+    pub final_scores: Option<crate::scoring::FinalScores>,
+    /// Commands the engine accepts right now; empty on a seat's device.
+    pub allowed: Vec<crate::engine::commands::CommandKind>,
 }
@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Activity, EngineState, NightActivity, Position, Role, Status, Team};
+use crate::engine::{Event, game};
+use crate::snapshot;
+
+/// Who is looking at the game.
+///
+/// Every view outside the host's own screen goes through a visibility so
+/// that hidden information (roles, shots, check results) cannot leak.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Visibility {
+    /// Sees everything
+    Host,
+    /// The room: roles are revealed only when a player leaves the game
+    Public,
+    /// The mafia team: teammates, shots and the Don's checks
+    Mafia,
+    /// The Sheriff: own checks, each seen as either Mafia or Citizen
+    Sheriff,
+    /// A single seat, seeing its own role plus whatever its team sees
+    Player(Position),
+}
+
+impl Visibility {
+    /// Resolves a seat to the group its role belongs to.
+    fn group(self, engine: &snapshot::Engine) -> Visibility {
+        let Visibility::Player(position) = self else {
+            return self;
+        };
+
+        match role_at(engine, position) {
+            Some(role) if role.team() == Team::Mafia => Visibility::Mafia,
+            Some(Role::Sheriff) => Visibility::Sheriff,
+            _ => Visibility::Public,
+        }
+    }
+
+    fn own_position(self) -> Option<Position> {
+        match self {
+            Visibility::Player(position) => Some(position),
+            _ => None,
+        }
+    }
+
+    /// Whether this audience may know who is awake right now.
+    ///
+    /// Night turns are taken in silence, so only the acting side learns
+    /// whose turn it is; only the card draw is open to everyone.
+    fn sees_actor(self, engine: &snapshot::Engine) -> bool {
+        let EngineState::Game(Activity::Night(activity)) = engine.state else {
+            return true;
+        };
+
+        let group = self.group(engine);
+        let own_role = self
+            .own_position()
+            .and_then(|position| role_at(engine, position));
+        match activity {
+            NightActivity::RoleAssignment => true,
+            NightActivity::DonReveal
+            | NightActivity::MafiaBriefing
+            | NightActivity::MafiaShooting
+            | NightActivity::DonCheck => group == Visibility::Mafia,
+            NightActivity::SheriffReveal | NightActivity::SheriffCheck => {
+                group == Visibility::Sheriff
+            }
+            NightActivity::ProstituteBlock => own_role == Some(Role::Prostitute),
+            NightActivity::DoctorHeal => own_role == Some(Role::Doctor),
+            NightActivity::ManiacKill => own_role == Some(Role::Maniac),
+        }
+    }
+
+    /// Copy of `engine` with everything this audience may not know removed.
+    ///
+    /// Once the game is decided nothing is hidden any more.
+    pub fn redact(self, engine: &snapshot::Engine) -> snapshot::Engine {
+        let group = self.group(engine);
+        if group == Visibility::Host || engine.result.is_some() {
+            return engine.clone();
+        }
+
+        let own = self.own_position();
+        let mut redacted = engine.clone();
+        if !self.sees_actor(engine) {
+            // A night turn's clock runs for the seat that is awake
+            redacted.actor.current = None;
+            redacted.timer = None;
+        }
+        for player in &mut redacted.game.players {
+            let is_own = player.position.is_some() && player.position == own;
+            let is_teammate =
+                group == Visibility::Mafia && player.role.map(|r| r.team()) == Some(Team::Mafia);
+
+            if !(is_own || is_teammate || player.status != Status::Alive) {
+                player.role = None;
+            }
+            if group != Visibility::Mafia {
+                player.shots = None;
+            }
+        }
+
+        for check in redacted.game.check.values_mut() {
+            if group != Visibility::Sheriff {
+                check.sheriff = None;
+            }
+            if group != Visibility::Mafia {
+                check.don = None;
+            }
+        }
+        redacted
+            .game
+            .check
+            .retain(|_, c| c.sheriff.is_some() || c.don.is_some());
+
+        // Night actions stay with whoever took them; only kills are public
+        let own_role = own.and_then(|position| role_at(engine, position));
+        for night in redacted.game.night.values_mut() {
+            if own_role != Some(Role::Prostitute) {
+                night.blocked = None;
+            }
+            if own_role != Some(Role::Doctor) {
+                night.healed = None;
+            }
+            if own_role != Some(Role::Maniac) {
+                night.maniac_shot = None;
+            }
+        }
+
+        // A check only tells which side a seat is on, not its exact card
+        for check in redacted.game.check.clone().values() {
+            if let Some(position) = check.sheriff {
+                reveal(&mut redacted, engine, position, |role| match role.team() {
+                    Team::Mafia => Role::Mafia,
+                    Team::Town | Team::Maniac | Team::Jester => Role::Citizen,
+                });
+            }
+            if let Some(position) = check.don {
+                reveal(&mut redacted, engine, position, |role| match role {
+                    Role::Sheriff => Role::Sheriff,
+                    _ => Role::Citizen,
+                });
+            }
+        }
+
+        redacted
+    }
+
+    /// Whether this audience may see `event`, judged against the state the
+    /// event was produced in.
+    pub fn can_see(self, engine: &snapshot::Engine, event: &Event) -> bool {
+        use game::check::Event as CheckEvent;
+        use game::night::Event as NightEvent;
+        use game::player::Event as PlayerEvent;
+
+        let group = self.group(engine);
+        if group == Visibility::Host {
+            return true;
+        }
+
+        match event {
+            Event::ActorAdvanced { .. } | Event::Timer(_) => self.sees_actor(engine),
+            Event::Game(game::Event::Player(
+                PlayerEvent::RoleAssigned { position, role, .. }
+                | PlayerEvent::RoleRevoked { position, role, .. },
+            )) => {
+                Some(*position) == self.own_position()
+                    || (group == Visibility::Mafia && role.team() == Team::Mafia)
+            }
+            Event::Game(game::Event::Player(PlayerEvent::Shooted { .. })) => {
+                group == Visibility::Mafia
+            }
+            Event::Game(game::Event::Check(CheckEvent::SheriffChecked { .. })) => {
+                group == Visibility::Sheriff
+            }
+            Event::Game(game::Event::Check(CheckEvent::DonChecked { .. })) => {
+                group == Visibility::Mafia
+            }
+            Event::Game(game::Event::Night(event)) => {
+                let actor = match event {
+                    NightEvent::Blocked { .. } => Role::Prostitute,
+                    NightEvent::Healed { .. } | NightEvent::Saved { .. } => Role::Doctor,
+                    NightEvent::ManiacShot { .. } => Role::Maniac,
+                    NightEvent::ManiacKill { .. } => return true,
+                };
+                self.own_position()
+                    .and_then(|position| role_at(engine, position))
+                    == Some(actor)
+            }
+            _ => true,
+        }
+    }
+}
+
+fn role_at(engine: &snapshot::Engine, position: Position) -> Option<Role> {
+    engine
+        .game
+        .players
+        .iter()
+        .find(|p| p.position == Some(position))
+        .and_then(|p| p.role)
+}
+
+/// Shows a hidden role of `redacted` as `seen` maps it from the real one.
+fn reveal(
+    redacted: &mut snapshot::Engine,
+    engine: &snapshot::Engine,
+    position: Position,
+    seen: impl Fn(Role) -> Role,
+) {
+    let Some(role) = role_at(engine, position) else {
+        return;
+    };
+    if let Some(player) = redacted
+        .game
+        .players
+        .iter_mut()
+        .find(|p| p.position == Some(position) && p.role.is_none())
+    {
+        player.role = Some(seen(role));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Engine, rules::RuleSet, timer};
+    use crate::snapshot::{Check, Snapshot};
+
+    fn player(position: u8, role: Role) -> snapshot::Player {
+        snapshot::Player {
+            name: format!("p{position}"),
+            position: Some(Position::new(position)),
+            role: Some(role),
+            is_silenced: false,
+            shots: None,
+            warnings: 0,
+            status: Status::Alive,
+        }
+    }
+
+    fn table() -> snapshot::Engine {
+        let mut engine = Engine::new(RuleSet::default()).snapshot();
+        engine.game.players = vec![
+            player(1, Role::Don),
+            player(2, Role::Mafia),
+            player(3, Role::Sheriff),
+            player(4, Role::Citizen),
+            player(5, Role::Citizen),
+        ];
+        engine.game.players[4].status = Status::Dead;
+        engine.game.players[0].shots = Some([(1, Position::new(5))].into());
+        engine.game.check.insert(
+            1,
+            Check {
+                sheriff: Some(Position::new(2)),
+                don: Some(Position::new(4)),
+            },
+        );
+        engine
+    }
+
+    fn roles(engine: &snapshot::Engine) -> Vec<Option<Role>> {
+        engine.game.players.iter().map(|p| p.role).collect()
+    }
+
+    #[test]
+    fn public_sees_roles_of_the_dead_only() {
+        let public = Visibility::Public.redact(&table());
+
+        assert_eq!(
+            roles(&public),
+            vec![None, None, None, None, Some(Role::Citizen)]
+        );
+        assert!(public.game.check.is_empty());
+        assert!(public.game.players[0].shots.is_none());
+    }
+
+    #[test]
+    fn teams_see_their_own_information() {
+        let engine = table();
+
+        let mafia = Visibility::Player(Position::new(2)).redact(&engine);
+        assert_eq!(
+            roles(&mafia),
+            vec![
+                Some(Role::Don),
+                Some(Role::Mafia),
+                None,
+                Some(Role::Citizen),
+                Some(Role::Citizen)
+            ]
+        );
+        assert!(mafia.game.players[0].shots.is_some());
+
+        // The Sheriff learns seat 2 is mafia, not which card it holds
+        let sheriff = Visibility::Sheriff.redact(&engine);
+        assert_eq!(sheriff.game.players[1].role, Some(Role::Mafia));
+        assert_eq!(sheriff.game.check[&1].don, None);
+
+        let citizen = Visibility::Player(Position::new(4)).redact(&engine);
+        assert_eq!(
+            roles(&citizen),
+            vec![None, None, None, Some(Role::Citizen), Some(Role::Citizen)]
+        );
+    }
+
+    #[test]
+    fn events_are_filtered_per_audience() {
+        let engine = table();
+        let dealt = Event::Game(game::Event::Player(game::player::Event::RoleAssigned {
+            name: "p1".to_string(),
+            position: Position::new(1),
+            role: Role::Don,
+        }));
+        let checked = Event::Game(game::Event::Check(game::check::Event::SheriffChecked {
+            chair: Position::new(2),
+        }));
+
+        assert!(!Visibility::Public.can_see(&engine, &dealt));
+        assert!(Visibility::Player(Position::new(2)).can_see(&engine, &dealt));
+        assert!(!Visibility::Player(Position::new(4)).can_see(&engine, &dealt));
+        assert!(Visibility::Sheriff.can_see(&engine, &checked));
+        assert!(!Visibility::Player(Position::new(1)).can_see(&engine, &checked));
+    }
+
+    #[test]
+    fn night_actor_is_hidden_from_everyone_else() {
+        let mut engine = table();
+        engine.state = EngineState::Game(Activity::Night(NightActivity::SheriffCheck));
+        engine.actor.current = Some(Position::new(3));
+        let advanced = Event::ActorAdvanced {
+            to: Position::new(3),
+        };
+
+        assert_eq!(Visibility::Public.redact(&engine).actor.current, None);
+        assert_eq!(
+            Visibility::Player(Position::new(1))
+                .redact(&engine)
+                .actor
+                .current,
+            None
+        );
+        assert_eq!(
+            Visibility::Sheriff.redact(&engine).actor.current,
+            Some(Position::new(3))
+        );
+        assert!(!Visibility::Public.can_see(&engine, &advanced));
+        assert!(Visibility::Player(Position::new(3)).can_see(&engine, &advanced));
+
+        // The reveals would give the Sheriff and the Don away to the room
+        engine.state = EngineState::Game(Activity::Night(NightActivity::SheriffReveal));
+        assert!(!Visibility::Public.can_see(&engine, &advanced));
+        assert!(Visibility::Sheriff.can_see(&engine, &advanced));
+        engine.state = EngineState::Game(Activity::Night(NightActivity::DonReveal));
+        engine.actor.current = Some(Position::new(1));
+        assert_eq!(Visibility::Public.redact(&engine).actor.current, None);
+        assert!(!Visibility::Sheriff.can_see(&engine, &advanced));
+        assert_eq!(
+            Visibility::Player(Position::new(2))
+                .redact(&engine)
+                .actor
+                .current,
+            Some(Position::new(1))
+        );
+
+        engine.state = EngineState::Game(Activity::Night(NightActivity::RoleAssignment));
+        assert!(Visibility::Public.can_see(&engine, &advanced));
+    }
+
+    #[test]
+    fn night_clock_does_not_name_the_seat_awake() {
+        let mut engine = table();
+        engine.state = EngineState::Game(Activity::Night(NightActivity::SheriffCheck));
+        engine.actor.current = Some(Position::new(3));
+        engine.timer = Some(snapshot::Timer {
+            speaker: Position::new(3),
+            seconds: 30,
+            paused: None,
+            speech: false,
+        });
+        let started = Event::Timer(timer::Event::Started {
+            speaker: Position::new(3),
+            seconds: 30,
+        });
+        let expired = Event::Timer(timer::Event::Expired {
+            speaker: Position::new(3),
+        });
+
+        assert_eq!(Visibility::Public.redact(&engine).timer, None);
+        assert_eq!(
+            Visibility::Player(Position::new(2)).redact(&engine).timer,
+            None
+        );
+        assert_eq!(Visibility::Sheriff.redact(&engine).timer, engine.timer);
+        assert!(!Visibility::Public.can_see(&engine, &started));
+        assert!(!Visibility::Public.can_see(&engine, &expired));
+        assert!(Visibility::Player(Position::new(3)).can_see(&engine, &started));
+    }
+}
@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::domain::{Position, Role, Status, Team};
+use crate::engine::result::GameResult;
+use crate::snapshot;
+
+/// Per-player summary of one finished game, as kept in the index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerRecord {
+    pub name: String,
+    pub role: Role,
+    pub won: bool,
+    pub warnings: u8,
+    #[serde(default)]
+    pub removed: bool,
+    pub shot_first_night: bool,
+    /// Checks made as Sheriff, and how many of them found a Mafia player.
+    pub checks: u8,
+    pub mafia_found: u8,
+    /// Mafia players named in the best guess, if this player made one.
+    pub guess_hits: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub winner: Team,
+    pub players: Vec<PlayerRecord>,
+}
+
+impl GameRecord {
+    /// Summarises a finished game; `None` while the game is undecided.
+    pub fn from_snapshot(engine: &snapshot::Engine) -> Option<Self> {
+        engine.result.map(|result| Self::new(&engine.game, &result))
+    }
+
+    pub fn new(game: &snapshot::Game, result: &GameResult) -> Self {
+        let role_at = |position: Position| {
+            game.players
+                .iter()
+                .find(|p| p.position == Some(position))
+                .and_then(|p| p.role)
+        };
+        let is_mafia =
+            |position: Position| role_at(position).map(|r| r.team()) == Some(Team::Mafia);
+
+        // The first shooting night belongs to day 1
+        let first_night_victim = game.kill.get(&1).copied();
+        let sheriff_checks: Vec<Position> = game.check.values().filter_map(|c| c.sheriff).collect();
+
+        let players = game
+            .players
+            .iter()
+            .filter_map(|p| {
+                let role = p.role?;
+                let shot_first_night = p.position.is_some() && p.position == first_night_victim;
+                let (checks, mafia_found) = if role == Role::Sheriff {
+                    (
+                        sheriff_checks.len() as u8,
+                        sheriff_checks.iter().filter(|c| is_mafia(**c)).count() as u8,
+                    )
+                } else {
+                    (0, 0)
+                };
+                let guess_hits = p
+                    .position
+                    .and_then(|position| game.guesses.get(&position))
+                    .map(|guess| guess.iter().filter(|g| is_mafia(**g)).count() as u8);
+
+                Some(PlayerRecord {
+                    name: p.name.clone(),
+                    role,
+                    won: role.team() == result.winner,
+                    warnings: p.warnings,
+                    removed: p.status == Status::Removed,
+                    shot_first_night,
+                    checks,
+                    mafia_found,
+                    guess_hits,
+                })
+            })
+            .collect();
+
+        Self {
+            winner: result.winner,
+            players,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoleStats {
+    pub games: u32,
+    pub wins: u32,
+}
+
+impl RoleStats {
+    pub fn win_rate(&self) -> f64 {
+        ratio(self.wins, self.games)
+    }
+}
+
+/// Totals for one player across every indexed game.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub by_role: HashMap<Role, RoleStats>,
+    pub checks: u32,
+    pub mafia_found: u32,
+    pub guesses: u32,
+    pub guess_hits: u32,
+    pub shot_first_night: u32,
+    pub warnings: u32,
+}
+
+impl PlayerStats {
+    pub fn win_rate(&self) -> f64 {
+        ratio(self.wins, self.games)
+    }
+
+    pub fn role(&self, role: Role) -> RoleStats {
+        self.by_role.get(&role).cloned().unwrap_or_default()
+    }
+
+    /// Share of Sheriff checks that found a Mafia player.
+    pub fn check_accuracy(&self) -> Option<f64> {
+        (self.checks > 0).then(|| ratio(self.mafia_found, self.checks))
+    }
+
+    pub fn warnings_per_game(&self) -> f64 {
+        ratio(self.warnings, self.games)
+    }
+
+    fn add(&mut self, record: &PlayerRecord) {
+        self.games += 1;
+        self.wins += record.won as u32;
+
+        let role = self.by_role.entry(record.role).or_default();
+        role.games += 1;
+        role.wins += record.won as u32;
+
+        self.checks += record.checks as u32;
+        self.mafia_found += record.mafia_found as u32;
+        if let Some(hits) = record.guess_hits {
+            self.guesses += 1;
+            self.guess_hits += hits as u32;
+        }
+        self.shot_first_night += record.shot_first_night as u32;
+        self.warnings += record.warnings as u32;
+    }
+}
+
+fn ratio(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// Append-only index of finished games, one JSON record per line.
+#[derive(Debug, Clone)]
+pub struct StatsIndex {
+    path: PathBuf,
+}
+
+impl StatsIndex {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Adds a finished game to the index. Undecided games are ignored.
+    pub fn ingest(&self, engine: &snapshot::Engine) -> anyhow::Result<()> {
+        let Some(record) = GameRecord::from_snapshot(engine) else {
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+
+        Ok(())
+    }
+
+    pub fn records(&self) -> anyhow::Result<Vec<GameRecord>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// Totals for `name`, or `None` if they never finished a game.
+    pub fn player(&self, name: &str) -> anyhow::Result<Option<PlayerStats>> {
+        let mut stats = PlayerStats {
+            name: name.to_string(),
+            ..PlayerStats::default()
+        };
+
+        for record in self.records()? {
+            for player in record.players.iter().filter(|p| p.name == name) {
+                stats.add(player);
+            }
+        }
+
+        Ok((stats.games > 0).then_some(stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DayIndex;
+    use crate::engine::{Engine, result::WinReason, rules::RuleSet};
+    use crate::snapshot::Snapshot;
+
+    fn player(name: &str, position: u8, role: Role) -> snapshot::Player {
+        snapshot::Player {
+            name: name.to_string(),
+            position: Some(Position::new(position)),
+            role: Some(role),
+            is_silenced: false,
+            shots: None,
+            warnings: 0,
+            status: Status::Alive,
+        }
+    }
+
+    fn finished_game() -> snapshot::Engine {
+        let mut engine = Engine::new(RuleSet::default()).snapshot();
+        engine.game.players = vec![
+            player("don", 1, Role::Don),
+            player("sheriff", 2, Role::Sheriff),
+            player("citizen", 3, Role::Citizen),
+        ];
+        engine.game.players[2].warnings = 2;
+        engine.game.kill.insert(1, Position::new(3));
+        engine
+            .game
+            .guesses
+            .insert(Position::new(3), vec![Position::new(1), Position::new(2)]);
+        engine.game.check.insert(
+            1,
+            snapshot::Check {
+                sheriff: Some(Position::new(1)),
+                don: None,
+            },
+        );
+        engine.game.check.insert(
+            2,
+            snapshot::Check {
+                sheriff: Some(Position::new(3)),
+                don: None,
+            },
+        );
+        engine.result = Some(GameResult {
+            winner: Team::Town,
+            reason: WinReason::MafiaEliminated,
+            day: DayIndex::new(2),
+        });
+        engine
+    }
+
+    #[test]
+    fn finished_games_are_summarised_per_player() {
+        let record = GameRecord::from_snapshot(&finished_game()).unwrap();
+
+        let citizen = &record.players[2];
+        assert!(citizen.won && citizen.shot_first_night);
+        assert_eq!(citizen.guess_hits, Some(1));
+
+        let sheriff = &record.players[1];
+        assert_eq!((sheriff.checks, sheriff.mafia_found), (2, 1));
+        assert!(!record.players[0].won);
+    }
+
+    #[test]
+    fn index_accumulates_player_stats() {
+        let path = std::env::temp_dir().join(format!("mafia-stats-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let index = StatsIndex::new(&path);
+
+        index.ingest(&finished_game()).unwrap();
+        index.ingest(&finished_game()).unwrap();
+        let stats = index.player("citizen").unwrap().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(stats.games, 2);
+        assert_eq!(stats.win_rate(), 1.0);
+        assert_eq!(stats.role(Role::Citizen).wins, 2);
+        assert_eq!((stats.guesses, stats.guess_hits), (2, 2));
+        assert_eq!(stats.warnings_per_game(), 2.0);
+        assert_eq!(stats.check_accuracy(), None);
+        assert!(index.player("nobody").unwrap().is_none());
+    }
+}
@@ -1,7 +1,12 @@
 use chrono::Local;
 use directories::ProjectDirs;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::app::log::Entry;
+use crate::engine::Engine;
+use crate::export;
+use crate::snapshot::{self, Restore, Snapshot};
 
 /// Returns ~/.local/share/mafia (or platform equivalent)
 fn app_data_dir() -> PathBuf {
@@ -25,3 +30,72 @@ pub fn timestamped_save_path() -> PathBuf {
 
     app_data_dir().join(filename)
 }
+
+/// Returns a unique timestamped event log path
+///
+/// Example:
+/// log_2026-01-28_21-14-03.jsonl
+pub fn timestamped_log_path() -> PathBuf {
+    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+    app_data_dir().join(format!("log_{timestamp}.jsonl"))
+}
+
+/// Returns the path of the statistics index of finished games
+pub fn stats_index_path() -> PathBuf {
+    app_data_dir().join("stats.jsonl")
+}
+
+/// Returns the path of a season's leaderboard
+pub fn leaderboard_path(season: &str) -> PathBuf {
+    app_data_dir().join(format!("leaderboard_{season}.json"))
+}
+
+/// Writes the engine, its journal and the game's event log as a JSON save
+/// file
+pub fn save_engine<P: AsRef<Path>>(engine: &Engine, log: &[Entry], path: P) -> anyhow::Result<()> {
+    let save = snapshot::Save {
+        engine: engine.snapshot(),
+        journal: Some(engine.journal().clone()),
+        log: log.to_vec(),
+    };
+
+    let json = serde_json::to_string_pretty(&save)?;
+    fs::write(path, json)?;
+
+    Ok(())
+}
+
+/// Writes the protocol of the engine's game, in the format named by the
+/// file extension unless `format` is given
+pub fn export_engine<P: AsRef<Path>>(
+    engine: &Engine,
+    path: P,
+    format: Option<export::Format>,
+) -> anyhow::Result<()> {
+    let format = format
+        .or_else(|| export::Format::from_path(&path))
+        .unwrap_or_default();
+    fs::write(path, export::render(&engine.snapshot(), format))?;
+
+    Ok(())
+}
+
+/// Reads a JSON save file back into a live engine
+pub fn load_engine<P: AsRef<Path>>(path: P) -> anyhow::Result<Engine> {
+    Ok(load_save(path)?.0)
+}
+
+/// Reads a JSON save file back into a live engine and its event log
+pub fn load_save<P: AsRef<Path>>(path: P) -> anyhow::Result<(Engine, Vec<Entry>)> {
+    let json = fs::read_to_string(path)?;
+    let save: snapshot::Save = serde_json::from_str(&json)?;
+
+    // Replaying keeps the seeded RNG and the journal itself intact,
+    // so the resumed game stays reproducible.
+    let engine = match &save.journal {
+        Some(journal) => Engine::replay(journal)?,
+        None => Engine::restore(&save.engine)?,
+    };
+
+    Ok((engine, save.log))
+}
@@ -45,3 +45,11 @@ pub fn draw(frame: &mut Frame, app: &snapshot::App) {
 
     crate::tui::widgets::draw(frame, &layout, &view);
 }
+
+/// Draws the read-only table shown to the room by a display client.
+pub fn draw_display(frame: &mut Frame, app: &snapshot::App, status: &str) {
+    let layout = Layout::new(frame.area());
+    let view = view::display::DisplayView::from_snapshot(app, status.to_string());
+
+    crate::tui::widgets::display::draw(frame, &layout, &view);
+}
@@ -4,6 +4,7 @@ pub mod events;
 pub mod host;
 pub mod lobby;
 pub mod main;
+pub mod panel;
 pub mod player;
 pub mod shell;
 pub mod table;
@@ -14,6 +15,7 @@ pub use events::Events;
 pub use host::Host;
 pub use lobby::Lobby;
 pub use main::Main;
+pub use panel::Panel;
 pub use player::Player;
 use ratatui::layout::Rect;
 pub use shell::Shell;
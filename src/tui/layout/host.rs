@@ -36,7 +36,7 @@ impl Host {
             Constraint::Length(1), // empty line
             Constraint::Length(1), // header
             Constraint::Min(9),    // main
-            Constraint::Length(1), // footer
+            Constraint::Length(2), // footer
             Constraint::Length(1), // empty line
         ])
         .split(area.inner(Margin {
@@ -0,0 +1,38 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// Centered panel with a header, body and footer, used by full screen views
+pub struct Panel {
+    pub header: Rect,
+    pub body: Rect,
+    pub footer: Rect,
+}
+
+impl Panel {
+    /// Create a PanelLayout centered in the given area
+    pub fn new(main_area: Rect) -> Self {
+        let width = main_area.width / 2;
+        let height = main_area.height * 2 / 3;
+
+        let panel = Rect {
+            x: main_area.x + (main_area.width - width) / 2,
+            y: main_area.y + (main_area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let rects = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(3),
+            ])
+            .split(panel);
+
+        Self {
+            header: rects[0],
+            body: rects[1],
+            footer: rects[2],
+        }
+    }
+}
@@ -12,13 +12,11 @@ impl Table {
     pub fn new(main: Rect, chair_count: usize) -> Self {
         let host = layout::Host::new(main);
 
-        let chair_w = main.width / 6;
+        // Bigger tables need narrower chairs to fit along the top and bottom
+        let chair_w = main.width / (chair_count.div_ceil(2) as u16 + 1).max(6);
         let chair_h = main.height / 6;
 
-        let mut chairs = calculate_chairs(main, host.area, chair_count, chair_w, chair_h);
-
-        sort_clockwise(&host.area, &mut chairs);
-        chairs.rotate_right(6);
+        let chairs = calculate_chairs(main, host.area, chair_count, chair_w, chair_h);
 
         let chairs = chairs.into_iter().map(layout::Chair::new).collect();
         Self { host, chairs }
@@ -40,8 +38,9 @@ fn calculate_chairs(table: Rect, host: Rect, n: usize, w: u16, h: u16) -> Vec<Re
     // Angle increment for n players around host
     let angle_step = 360.0 / n as f64;
 
+    // Seat 1 sits just left of the bottom centre, the rest follow clockwise
     for i in 0..n {
-        let angle_deg = i as f64 * angle_step;
+        let angle_deg = 90.0 + (i as f64 + 0.5) * angle_step;
         let angle_rad = angle_deg.to_radians();
 
         // Calculate card center position
@@ -66,54 +65,3 @@ fn calculate_chairs(table: Rect, host: Rect, n: usize, w: u16, h: u16) -> Vec<Re
 
     areas
 }
-
-fn center_of(rect: &Rect) -> (f64, f64) {
-    let cx = rect.x as f64 + rect.width as f64 / 2.0;
-    let cy = rect.y as f64 + rect.height as f64 / 2.0;
-    (cx, cy)
-}
-
-fn angle_from_host(host: &Rect, player: &Rect) -> f64 {
-    let (host_cx, host_cy) = center_of(host);
-
-    // Find the corner of player closest to host center
-    let corners = [
-        (player.x as f64, player.y as f64),
-        (player.x as f64 + player.width as f64, player.y as f64),
-        (player.x as f64, player.y as f64 + player.height as f64),
-        (
-            player.x as f64 + player.width as f64,
-            player.y as f64 + player.height as f64,
-        ),
-    ];
-
-    let closest = corners
-        .iter()
-        .min_by(|a, b| {
-            let da = (a.0 - host_cx).powi(2) + (a.1 - host_cy).powi(2);
-            let db = (b.0 - host_cx).powi(2) + (b.1 - host_cy).powi(2);
-            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .unwrap();
-
-    let dx = closest.0 - host_cx;
-    let dy = closest.1 - host_cy;
-
-    // atan2 returns [-pi, pi], 0 is to the right (east), +y is down
-    let mut angle = dy.atan2(dx);
-
-    // Shift reference so 0 is left-bottom
-    angle -= -std::f64::consts::FRAC_PI_4; // left-bottom is -pi/4 from east
-    if angle < 0.0 {
-        angle += 2.0 * std::f64::consts::PI;
-    }
-
-    angle
-}
-fn sort_clockwise(host: &Rect, chairs: &mut [Rect]) {
-    chairs.sort_by(|a, b| {
-        angle_from_host(host, a)
-            .partial_cmp(&angle_from_host(host, b))
-            .unwrap()
-    });
-}
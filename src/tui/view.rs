@@ -1,21 +1,26 @@
 pub mod chair;
 pub mod command;
+pub mod display;
+pub mod end;
 pub mod events;
 pub mod host;
 pub mod lobby;
 pub mod main;
 pub mod player;
 pub mod shell;
+pub mod stats;
 pub mod table;
 
 pub use chair::ChairView;
 pub use command::CommandView;
+pub use end::EndView;
 pub use events::EventsView;
 pub use host::HostView;
 pub use lobby::LobbyView;
 pub use main::MainView;
 pub use player::PlayerView;
 pub use shell::Shell;
+pub use stats::StatsView;
 pub use table::TableView;
 
 #[derive(Debug, Clone)]
@@ -62,7 +62,7 @@ impl ChairView {
                             ChairState::Muted
                         }
                         // 3️⃣ Active player states
-                        else if app.engine.actor == Some(position) {
+                        else if app.engine.actor.current == Some(position) {
                             match app.engine.phase.unwrap().daytime() {
                                 Night => ChairState::RoleAssignment,
                                 Morning | Noon => ChairState::Speaking,
@@ -79,7 +79,7 @@ impl ChairView {
         };
 
         // Active actor is always highlighted
-        if app.engine.actor == Some(position) {
+        if app.engine.actor.current == Some(position) {
             highlight = true;
         }
 
@@ -87,9 +87,9 @@ impl ChairView {
         let is_mafia_briefing = matches!(phase, Activity::Night(NightActivity::MafiaBriefing));
         let is_final_voting = matches!(phase, Activity::Evening(EveningActivity::FinalVoting));
 
-        if is_mafia_briefing && app.engine.actor.is_some() {
+        if is_mafia_briefing && app.engine.actor.current.is_some() {
             if let Some(view) = &player_view {
-                if view.role.expect("Role must exist") == Role::Mafia {
+                if view.role == Some(Role::Mafia) {
                     highlight = true;
                 }
             }
@@ -100,9 +100,9 @@ impl ChairView {
                 if app
                     .engine
                     .game
-                    .tie_voting
+                    .final_vote
                     .get(&app.engine.day)
-                    .map_or(false, |voting| voting.nominees.contains(&position))
+                    .map_or(false, |vote| vote.candidates.contains(&position))
                 {
                     highlight = true;
                 }
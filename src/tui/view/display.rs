@@ -0,0 +1,163 @@
+use ratatui::style::Color;
+
+use crate::{
+    domain::{Day, Position, Status},
+    snapshot::{self, Visibility},
+    tui::view::{ChairView, EventsView, LobbyView, chair::ChairState, host::Header},
+};
+
+/// Read-only table for the room: no roles, no host controls.
+#[derive(Debug, Clone)]
+pub struct DisplayView {
+    pub main: DisplayMain,
+    pub events: EventsView,
+    pub status: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum DisplayMain {
+    Lobby(LobbyView),
+    Table {
+        board: BoardView,
+        chairs: Vec<ChairView>,
+    },
+}
+
+/// What sits in the middle of the table instead of the host panel.
+#[derive(Debug, Clone)]
+pub struct BoardView {
+    pub title: String,
+    pub title_style: Color,
+    pub header: Header,
+    pub lines: Vec<String>,
+}
+
+impl DisplayView {
+    pub fn from_snapshot(app: &snapshot::App, status: String) -> Self {
+        // The room sees the actor exactly when a public client would
+        let actor = Visibility::Public.redact(&app.engine).actor.current;
+        let main = match app.engine.phase {
+            None => DisplayMain::Lobby(LobbyView::from_snapshot(app)),
+            Some(_) => DisplayMain::Table {
+                board: BoardView::from_snapshot(app, actor),
+                chairs: (1u8..=app.engine.game.rules.player_count)
+                    .map(|i| {
+                        let mut chair = ChairView::from_snapshot(i.into(), app);
+                        if actor.is_none() && app.engine.actor.current == Some(chair.position) {
+                            chair.highlight = false;
+                            chair.state = ChairState::Alive;
+                        }
+                        chair
+                    })
+                    .collect(),
+            },
+        };
+
+        Self {
+            main,
+            events: EventsView::from_snapshot(app),
+            status,
+        }
+    }
+}
+
+impl BoardView {
+    fn from_snapshot(app: &snapshot::App, actor: Option<Position>) -> Self {
+        let engine = &app.engine;
+        let phase = engine.phase.expect("phase must exist");
+
+        let (title, title_style) = match phase.daytime() {
+            Day::Night => (format!("🌙 Night · {}", engine.day), Color::Magenta),
+            Day::Morning => (format!("☀ Morning · {}", engine.day), Color::Cyan),
+            Day::Noon => (format!("☀ Day · {}", engine.day), Color::Yellow),
+            Day::Evening => (format!("🌆 Evening · {}", engine.day), Color::Blue),
+        };
+
+        let players = &engine.game.players;
+        let in_players = players.iter().filter(|p| p.status == Status::Alive).count();
+
+        let mut lines = Vec::new();
+        if let Some(position) = actor {
+            lines.push(format!("Now: {position}"));
+        }
+        if let Some(seconds) = app.current_timer {
+            lines.push(format!("⏱ {seconds}s"));
+        }
+        if let Some(result) = &engine.result {
+            lines.push(result.to_string());
+        }
+
+        Self {
+            title,
+            title_style,
+            header: Header::new(in_players, players.len() - in_players, phase.to_string()),
+            lines,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Activity, EngineState, NightActivity, Role};
+    use crate::engine::{Engine, rules::RuleSet};
+    use crate::snapshot::Snapshot;
+
+    fn table(activity: NightActivity) -> snapshot::App {
+        let mut engine = Engine::new(RuleSet::default()).snapshot();
+        engine.game.players = (1..=engine.game.rules.player_count)
+            .map(|i| snapshot::Player {
+                name: format!("p{i}"),
+                position: Some(Position::new(i)),
+                role: Some(Role::Citizen),
+                is_silenced: false,
+                shots: None,
+                warnings: 0,
+                status: Status::Alive,
+            })
+            .collect();
+        engine.phase = Some(Activity::Night(activity));
+        engine.state = EngineState::Game(Activity::Night(activity));
+        engine.actor.current = Some(Position::new(4));
+
+        snapshot::App {
+            engine,
+            input: String::new(),
+            input_mode: crate::app::input::InputMode::Normal,
+            events: Vec::new(),
+            current_timer: None,
+            stats: None,
+            final_scores: None,
+            allowed: Vec::new(),
+        }
+    }
+
+    fn table_of(view: &DisplayView) -> (&BoardView, &[ChairView]) {
+        match &view.main {
+            DisplayMain::Table { board, chairs } => (board, chairs),
+            DisplayMain::Lobby(_) => panic!("expected the table"),
+        }
+    }
+
+    #[test]
+    fn night_actor_is_not_shown_to_the_room() {
+        let view = DisplayView::from_snapshot(&table(NightActivity::SheriffCheck), String::new());
+        let (board, chairs) = table_of(&view);
+
+        assert!(!board.lines.iter().any(|line| line.starts_with("Now:")));
+        assert!(chairs.iter().all(|chair| !chair.highlight));
+
+        // The reveal would point the room at the Sheriff
+        let view = DisplayView::from_snapshot(&table(NightActivity::SheriffReveal), String::new());
+        let (board, chairs) = table_of(&view);
+
+        assert!(!board.lines.iter().any(|line| line.starts_with("Now:")));
+        assert!(chairs.iter().all(|chair| !chair.highlight));
+
+        let view = DisplayView::from_snapshot(&table(NightActivity::RoleAssignment), String::new());
+        let (board, chairs) = table_of(&view);
+
+        assert!(board.lines.contains(&"Now: 4".to_string()));
+        assert!(chairs[3].highlight);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{domain::Role, snapshot};
+
+#[derive(Debug, Clone)]
+pub struct EndView {
+    pub title: String,
+    pub subtitle: String,
+    pub season: String,
+    pub rows: Vec<ScoreRowView>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoreRowView {
+    pub name: String,
+    pub role: Role,
+    pub won: bool,
+    pub points: f64,
+    pub season_rank: Option<usize>,
+    pub season_points: f64,
+}
+
+impl EndView {
+    pub fn from_snapshot(app: &snapshot::App) -> Option<Self> {
+        let scores = app.final_scores.as_ref()?;
+
+        let rows = scores
+            .scores
+            .iter()
+            .map(|s| {
+                let standing = scores.season_standings.get(&s.name);
+                ScoreRowView {
+                    name: s.name.clone(),
+                    role: s.role,
+                    won: s.won,
+                    points: s.points,
+                    season_rank: standing.map(|st| st.rank),
+                    season_points: standing.map(|st| st.points).unwrap_or(0.0),
+                }
+            })
+            .collect();
+
+        Some(Self {
+            title: format!("{} wins", scores.result.winner),
+            subtitle: format!("Day {}, {}", scores.result.day, scores.result.reason),
+            season: scores.season.clone(),
+            rows,
+        })
+    }
+}
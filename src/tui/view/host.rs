@@ -12,11 +12,23 @@ pub use main::Main;
 use crate::app::input::InputMode;
 use crate::domain::{
     Activity, Day, EveningActivity, MorningActivity, NightActivity, NoonActivity, Position, Role,
-    Status,
+    Status, Team,
 };
 use crate::snapshot::{self, Check, Player};
 use ratatui::style::Color;
 
+/// The seat every living, unblocked mafioso has shot so far tonight.
+fn mafia_shot(players: &[Player], day: usize, blocked: Option<Position>) -> Option<Position> {
+    let mut shots = players
+        .iter()
+        .filter(|p| p.status == Status::Alive && p.role.is_some_and(|r| r.team() == Team::Mafia))
+        .filter(|p| p.position != blocked)
+        .map(|p| p.shots.as_ref().and_then(|shots| shots.get(&day)).copied());
+
+    let target = shots.next()??;
+    shots.all(|shot| shot == Some(target)).then_some(target)
+}
+
 #[derive(Debug, Clone)]
 pub struct HostView {
     pub title: String, // 🌙 NIGHT · Day 2
@@ -46,9 +58,9 @@ impl HostView {
             .players
             .iter()
             .fold(0, |c, p| if p.status == Status::Alive { c + 1 } else { c });
-        let out_p_c = 10 - in_p_c;
+        let out_p_c = engine.game.players.len() - in_p_c;
 
-        let body = if engine.actor.is_some() {
+        let body = if engine.actor.current.is_some() {
             Main::Actor(Actor::new(
                 host_text.actor.expect("actor should exist at this point"),
                 host_text.timer,
@@ -61,15 +73,35 @@ impl HostView {
         Self {
             title,
             title_style,
-            header: Header::new(in_p_c, out_p_c, host_text.title),
+            header: Header::new(in_p_c, out_p_c, host_text.title).tally(tally(engine, phase)),
             body,
-            footer: Footer::new(&host_text.info.expect("info should exist")),
+            footer: Footer::new(&host_text.info.expect("info should exist"), &app.allowed),
             input: app.input.clone(),
             input_mode: app.input_mode.clone(),
         }
     }
 }
 
+/// Live votes per nominee, in nomination order, while a vote is running.
+fn tally(engine: &snapshot::Engine, phase: Activity) -> Option<String> {
+    let voting = match phase {
+        Activity::Evening(EveningActivity::Voting) => engine.game.voting.get(&engine.day),
+        Activity::Evening(EveningActivity::TieVoting) => engine.game.tie_voting.get(&engine.day),
+        _ => None,
+    }?;
+    if voting.tally.is_empty() {
+        return None;
+    }
+
+    let tally = voting
+        .tally
+        .iter()
+        .map(|(nominee, votes)| format!("{nominee}:{votes}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(tally)
+}
+
 pub struct Text {
     // Header
     pub title: String,
@@ -139,9 +171,12 @@ impl Text {
         let day = app.engine.day;
         let timer = app.current_timer;
         let checks = app.engine.game.check.clone();
-        let actor = app.engine.actor;
+        let night = app.engine.game.night.get(&day).cloned().unwrap_or_default();
+        let actor = app.engine.actor.current;
         let players = app.engine.game.players.as_slice();
-        let guesses = app.engine.game.guess.as_slice();
+        let guesses = actor
+            .and_then(|a| app.engine.game.guesses.get(&a))
+            .map_or(&[][..], Vec::as_slice);
         let voting = app
             .engine
             .game
@@ -156,13 +191,13 @@ impl Text {
             .get(&day)
             .unwrap_or(&snapshot::Voting::default())
             .clone();
-        let final_votes = app
+        let final_vote = app
             .engine
             .game
-            .final_voting
+            .final_vote
             .get(&day)
-            .unwrap_or(&Vec::new())
-            .clone();
+            .cloned()
+            .unwrap_or_default();
         let eliminations = app
             .engine
             .game
@@ -172,7 +207,7 @@ impl Text {
             .clone();
 
         match phase {
-            Night(activity) => Self::night(activity, day, actor, players, timer, checks),
+            Night(activity) => Self::night(activity, day, actor, players, timer, checks, &night),
             Morning(activity) => Self::morning(activity, actor, players, timer, guesses),
             Noon(activity) => Self::noon(
                 activity,
@@ -189,7 +224,7 @@ impl Text {
                 timer,
                 voting,
                 tie_voting,
-                final_votes.as_slice(),
+                &final_vote,
                 eliminations.as_slice(),
             ),
         }
@@ -202,8 +237,10 @@ impl Text {
         players: &[Player],
         timer: Option<u64>,
         checks: HashMap<usize, Check>,
+        night: &snapshot::Night,
     ) -> Self {
         use NightActivity::*;
+        let shot = mafia_shot(players, day, night.blocked);
         match activity {
             RoleAssignment => Self::role_assignment(actor, players),
 
@@ -215,11 +252,33 @@ impl Text {
 
             MafiaBriefing => Self::mafia_briefing(actor, players, timer),
 
+            ProstituteBlock => Self::night_action(
+                Role::Prostitute,
+                "block",
+                actor,
+                players,
+                timer,
+                night.blocked,
+            ),
+
             MafiaShooting => Self::mafia_shooting(actor, players, day),
 
-            SheriffCheck => Self::check(day, Role::Sheriff, actor, players, timer, checks),
+            ManiacKill => Self::night_action(
+                Role::Maniac,
+                "shoot",
+                actor,
+                players,
+                timer,
+                night.maniac_shot,
+            ),
+
+            DoctorHeal => {
+                Self::night_action(Role::Doctor, "heal", actor, players, timer, night.healed)
+            }
 
-            DonCheck => Self::check(day, Role::Don, actor, players, timer, checks),
+            SheriffCheck => Self::check(day, Role::Sheriff, actor, players, timer, checks, shot),
+
+            DonCheck => Self::check(day, Role::Don, actor, players, timer, checks, shot),
         }
     }
 
@@ -258,7 +317,7 @@ impl Text {
         timer: Option<u64>,
         voting: snapshot::Voting,
         tie_voting: snapshot::Voting,
-        final_votes: &[Position],
+        final_vote: &snapshot::FinalVote,
         eliminations: &[Position],
     ) -> Self {
         use EveningActivity::*;
@@ -271,7 +330,7 @@ impl Text {
             Voting => Self::voting(actor, players, timer, nominees, votes),
             TieDiscussion => Self::tie_discussion(actor, players, timer, &tie_voting.nominees),
             TieVoting => Self::tie_voting(actor, players, timer, tie_nominees, tie_votes),
-            FinalVoting => Self::final_voting(timer, tie_nominees, final_votes),
+            FinalVoting => Self::final_voting(timer, final_vote),
             FinalSpeech => Self::final_speech(actor, players, timer, eliminations),
         }
     }
@@ -426,6 +485,45 @@ impl Text {
         builder.build()
     }
 
+    /// Turn of a single-actor night role, such as the Doctor's heal.
+    fn night_action(
+        role: Role,
+        verb: &str,
+        actor: Option<Position>,
+        players: &[Player],
+        timer: Option<u64>,
+        target: Option<Position>,
+    ) -> Text {
+        let mut builder =
+            TextBuilder::new(format!("{role} Turn")).info(&format!("The {role} acts alone"));
+
+        match actor {
+            None => {
+                builder = builder.description(format!(
+                    "Wake the {role} to choose a player.\n\
+                     Use `{verb} n` to record the choice.\n\
+                     Run `next` to select the {role}",
+                ))
+            }
+            Some(position) => {
+                let name = players
+                    .iter()
+                    .find(|p| p.position == Some(position))
+                    .map_or("", |p| p.name.as_str());
+
+                builder = builder
+                    .actor(format!("{name} at {position} is the {role}."))
+                    .timer(timer)
+                    .result(match target {
+                        Some(target) => format!("Recorded {verb} {target}"),
+                        None => "Nothing recorded yet.".to_string(),
+                    });
+            }
+        }
+
+        builder.build()
+    }
+
     fn check(
         day: usize,
         role: Role, // Sheriff or Don
@@ -433,6 +531,7 @@ impl Text {
         players: &[Player],
         timer: Option<u64>,
         checks: HashMap<usize, Check>,
+        shot: Option<Position>,
     ) -> Text {
         // Title based on role
         let title = match role {
@@ -460,7 +559,18 @@ impl Text {
             String::new()
         };
 
-        let mut builder = TextBuilder::new(title).info(&subtitle);
+        // Checks follow the shooting; the shot itself lands at dawn
+        let shooting = match shot {
+            Some(position) => format!("Mafia shot {position}"),
+            None => "Mafia missed".to_string(),
+        };
+        let info = if subtitle.is_empty() {
+            shooting
+        } else {
+            format!("{shooting} · {subtitle}")
+        };
+
+        let mut builder = TextBuilder::new(title).info(&info);
 
         match actor {
             None => {
@@ -799,37 +909,40 @@ impl Text {
         builder.build()
     }
 
-    fn final_voting(timer: Option<u64>, nominees: &[Position], final_votes: &[Position]) -> Text {
-        // Prepare subtitle with tied nominees
-        let nominees_list = nominees
-            .iter()
-            .map(|p| p.value().to_string())
-            .collect::<Vec<_>>()
-            .join(", ");
+    fn final_voting(timer: Option<u64>, vote: &snapshot::FinalVote) -> Text {
+        let list = |positions: &[Position]| {
+            positions
+                .iter()
+                .map(|p| p.value().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let candidates = list(&vote.candidates);
 
-        let subtitle = if nominees.is_empty() {
+        let subtitle = if vote.candidates.is_empty() {
             "No tied nominees".to_string()
         } else {
-            format!("Tied nominees: {nominees_list}")
+            format!("Lift all: {candidates}")
         };
 
         let mut builder = TextBuilder::new("Final Voting").info(&subtitle);
 
-        if final_votes.is_empty() {
+        if vote.yes.is_empty() {
             builder = builder.description(format!(
-                "The following players were in tie voting:\n{nominees_list}\n\
-                     Record final votes using `vote <positions>` (use 0 if none).\n\
-                     Run `next` to move forward without recording votes."
+                "The tie could not be broken between:\n{candidates}\n\
+                     Should all of them leave the game?\n\
+                     Record yes votes using `vote <positions>`; everyone else votes no.\n\
+                     Run `next` to count the votes."
             ))
         } else {
-            builder = builder.description(format!(
-                "Players voted: {}",
-                final_votes
-                    .iter()
-                    .map(|p| p.value().to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ));
+            let majority = vote.voters.len() / 2 + 1;
+            builder = builder
+                .description(format!("Yes: {}\nNo: {}", list(&vote.yes), list(&vote.no)))
+                .result(format!(
+                    "{} of {} yes, {majority} needed to lift all",
+                    vote.yes.len(),
+                    vote.voters.len()
+                ));
         }
 
         builder.build()
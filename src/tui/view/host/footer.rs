@@ -1,12 +1,50 @@
+use crate::engine::commands::CommandKind;
+
 #[derive(Debug, Clone)]
 pub struct Footer {
     pub info: String, // list of host commands
+    /// Commands the host may type right now.
+    pub commands: String,
 }
 
 impl Footer {
-    pub fn new(info: &str) -> Self {
+    pub fn new(info: &str, allowed: &[CommandKind]) -> Self {
         Self {
             info: info.to_string(),
+            commands: allowed
+                .iter()
+                .filter_map(usage)
+                .collect::<Vec<_>>()
+                .join(" · "),
         }
     }
 }
+
+/// How the host types `kind`; `None` for what the app sends by itself.
+fn usage(kind: &CommandKind) -> Option<&'static str> {
+    let usage = match kind {
+        CommandKind::Join => "join <name>",
+        CommandKind::Leave => "leave <name>",
+        CommandKind::ResizeTable => "table <seats>",
+        CommandKind::SwapSeats => "swap <from> <to>",
+        CommandKind::Start => "start",
+        CommandKind::Advance => "next",
+        CommandKind::AssignRole => "assign role",
+        CommandKind::RevokeRole => "revoke-role",
+        CommandKind::DealRole { .. } => "assign role <role>",
+        CommandKind::Warn { .. } => "warn <seat>",
+        CommandKind::Pardon { .. } => "pardon <seat>",
+        CommandKind::Nominate { .. } => "nominate <seat>",
+        CommandKind::Vote { .. } => "vote <seats>",
+        CommandKind::Shoot { .. } => "shoot <seat>",
+        CommandKind::Check { .. } => "check <seat>",
+        CommandKind::Heal { .. } => "heal <seat>",
+        CommandKind::Block { .. } => "block <seat>",
+        CommandKind::Guess { .. } => "guess <seats>",
+        CommandKind::PauseTimer => "pause",
+        CommandKind::ResumeTimer => "resume",
+        CommandKind::ExtendTimer => "extend <seconds>",
+        CommandKind::ExpireTimer => return None,
+    };
+    Some(usage)
+}
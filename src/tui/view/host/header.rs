@@ -2,7 +2,8 @@
 pub struct Header {
     pub in_players: usize,
     pub out_players: usize,
-    pub activity: String, // current activity, e.g. Discussion
+    pub activity: String,      // current activity, e.g. Discussion
+    pub tally: Option<String>, // votes per nominee while voting, e.g. 3:4 7:5
 }
 
 impl Header {
@@ -11,6 +12,12 @@ impl Header {
             in_players,
             out_players,
             activity,
+            tally: None,
         }
     }
+
+    pub fn tally(mut self, tally: Option<String>) -> Self {
+        self.tally = tally;
+        self
+    }
 }
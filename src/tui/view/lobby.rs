@@ -46,20 +46,20 @@ impl LobbyView {
         let player_count = players_vec.len();
         let assigned_positions: Vec<u8> = players_vec.iter().filter_map(|p| p.position).collect();
 
-        const MAX_PLAYERS: u8 = 10;
+        let max_players = app.engine.game.rules.player_count;
 
-        let available_positions: Vec<u8> = (1..=MAX_PLAYERS)
+        let available_positions: Vec<u8> = (1..=max_players)
             .filter(|p| !assigned_positions.contains(p))
             .collect();
 
-        let ready = players_vec.len() == MAX_PLAYERS as usize
+        let ready = players_vec.len() == max_players as usize
             && players_vec.iter().all(|p| p.position.is_some());
 
         Self {
             title,
             players: players_vec,
             player_count,
-            max_players: MAX_PLAYERS,
+            max_players,
             available_positions,
             ready,
             input: app.input.clone(),
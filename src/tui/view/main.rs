@@ -1,17 +1,26 @@
 use crate::{
     domain::EngineState,
     snapshot,
-    tui::view::{LobbyView, TableView},
+    tui::view::{EndView, LobbyView, StatsView, TableView},
 };
 
 #[derive(Debug, Clone)]
 pub enum MainView {
     Lobby(LobbyView),
     Table(TableView),
+    Stats(StatsView),
+    End(EndView),
 }
 
 impl MainView {
     pub fn from_snapshot(app: &snapshot::App) -> Self {
+        if let Some(stats) = StatsView::from_snapshot(app) {
+            return MainView::Stats(stats);
+        }
+        if let Some(end) = EndView::from_snapshot(app) {
+            return MainView::End(end);
+        }
+
         match app.engine.state {
             EngineState::Lobby(_) => MainView::Lobby(LobbyView::from_snapshot(app)),
             _ => MainView::Table(TableView::from_snapshot(app)),
@@ -0,0 +1,63 @@
+use crate::{domain::Role, snapshot, stats::PlayerStats};
+
+#[derive(Debug, Clone)]
+pub struct StatsView {
+    pub name: String,
+    pub games: u32,
+    pub win_rate: f64,
+    pub roles: Vec<RoleStatsView>,
+    pub check_accuracy: Option<f64>,
+    pub guesses: u32,
+    pub guess_hits: u32,
+    pub shot_first_night: u32,
+    pub warnings_per_game: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoleStatsView {
+    pub role: Role,
+    pub games: u32,
+    pub win_rate: f64,
+}
+
+impl StatsView {
+    pub fn from_snapshot(app: &snapshot::App) -> Option<Self> {
+        app.stats.as_ref().map(Self::from_stats)
+    }
+
+    fn from_stats(stats: &PlayerStats) -> Self {
+        let roles = [
+            Role::Citizen,
+            Role::Sheriff,
+            Role::Doctor,
+            Role::Prostitute,
+            Role::Mafia,
+            Role::Don,
+            Role::Maniac,
+            Role::Jester,
+        ]
+        .into_iter()
+        .map(|role| {
+            let played = stats.role(role);
+            RoleStatsView {
+                role,
+                games: played.games,
+                win_rate: played.win_rate(),
+            }
+        })
+        .filter(|r| r.games > 0)
+        .collect();
+
+        Self {
+            name: stats.name.clone(),
+            games: stats.games,
+            win_rate: stats.win_rate(),
+            roles,
+            check_accuracy: stats.check_accuracy(),
+            guesses: stats.guesses,
+            guess_hits: stats.guess_hits,
+            shot_first_night: stats.shot_first_night,
+            warnings_per_game: stats.warnings_per_game(),
+        }
+    }
+}
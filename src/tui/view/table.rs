@@ -11,7 +11,7 @@ impl TableView {
     pub fn from_snapshot(app: &snapshot::App) -> Self {
         let host = HostView::from_snapshot(app);
 
-        let chairs = (1u8..=10) // or Game::PLAYER_COUNT if exposed
+        let chairs = (1u8..=app.engine.game.rules.player_count)
             .map(|i| {
                 let position = i.into();
                 ChairView::from_snapshot(position, app)
@@ -1,11 +1,14 @@
 pub mod chair;
 pub mod command;
+pub mod display;
+pub mod end;
 pub mod events;
 pub mod host;
 pub mod lobby;
 pub mod main;
 pub mod player;
 pub mod popup;
+pub mod stats;
 pub mod table;
 
 use ratatui::Frame;
@@ -0,0 +1,71 @@
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+
+use crate::tui::{
+    layout::{self, Layout},
+    view::display::{BoardView, DisplayMain, DisplayView},
+    widgets::{chair, events, host, lobby},
+};
+
+pub fn draw(frame: &mut Frame, layout: &Layout, view: &DisplayView) {
+    let main = &layout.screen.main;
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" TABLE ")
+            .style(Style::default().fg(Color::Green)),
+        main.area,
+    );
+
+    match &view.main {
+        DisplayMain::Lobby(lobby_view) => {
+            let lobby_layout = layout::Lobby::new(main.content);
+            lobby::draw(frame, &lobby_layout, lobby_view).unwrap();
+        }
+        DisplayMain::Table { board, chairs } => {
+            let table_layout = layout::Table::new(main.content, chairs.len());
+            draw_board(frame, &table_layout.host, board);
+            for (chair_layout, chair_view) in table_layout.chairs.iter().zip(chairs) {
+                chair::draw(frame, chair_layout, chair_view);
+            }
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(view.status.as_str()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Display")
+                .style(Style::default().fg(Color::Cyan)),
+        ),
+        layout.screen.command.area,
+    );
+
+    events::draw(frame, &layout.screen.events, &view.events);
+}
+
+fn draw_board(frame: &mut Frame, layout: &layout::Host, view: &BoardView) {
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(view.title.clone())
+            .title_alignment(Alignment::Center)
+            .style(view.title_style),
+        layout.area,
+    );
+
+    host::header::draw(frame, &layout.header, &view.header).unwrap();
+
+    let lines: Vec<Line> = view
+        .lines
+        .iter()
+        .map(|l| Line::from(l.as_str()).alignment(Alignment::Center))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), layout.body.desc.area);
+}
@@ -0,0 +1,64 @@
+use crate::tui::layout;
+use crate::tui::view::EndView;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+pub fn draw(frame: &mut Frame, area: &layout::Panel, view: &EndView) {
+    // ===== Header =====
+    let header = Paragraph::new(vec![
+        Line::from(Span::styled(
+            &view.title,
+            Style::default().add_modifier(Modifier::BOLD),
+        ))
+        .alignment(Alignment::Center),
+        Line::from(view.subtitle.as_str()).alignment(Alignment::Center),
+    ])
+    .block(Block::default().borders(Borders::ALL));
+
+    frame.render_widget(header, area.header);
+
+    // ===== Scores =====
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:<14} {:<8} {:>6} {:>8} {:>5}",
+            "Player", "Role", "Points", "Season", "Rank"
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for row in &view.rows {
+        let style = if row.won {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{:<14} {:<8} {:>6.2} {:>8.2} {:>5}",
+                row.name,
+                row.role.to_string(),
+                row.points,
+                row.season_points,
+                row.season_rank.map(|r| r.to_string()).unwrap_or_default()
+            ),
+            style,
+        )));
+    }
+
+    let body = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Scores · season {}", view.season)),
+    );
+    frame.render_widget(body, area.body);
+
+    // ===== Footer =====
+    let footer = Paragraph::new("Esc to start a new game")
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, area.footer);
+}
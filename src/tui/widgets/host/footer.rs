@@ -3,6 +3,7 @@ use ratatui::{
     Frame,
     layout::Alignment,
     style::{Modifier, Stylize},
+    text::Line,
     widgets::Paragraph,
 };
 
@@ -11,9 +12,12 @@ pub fn draw(
     layout: &layout::host::Footer,
     view: &view::host::Footer,
 ) -> anyhow::Result<()> {
-    let paragraph = Paragraph::new(view.info.clone())
-        .alignment(Alignment::Center)
-        .add_modifier(Modifier::ITALIC);
+    let paragraph = Paragraph::new(vec![
+        Line::from(view.info.clone()),
+        Line::from(view.commands.clone()).dim(),
+    ])
+    .alignment(Alignment::Center)
+    .add_modifier(Modifier::ITALIC);
 
     frame.render_widget(paragraph, layout.area);
     Ok(())
@@ -15,7 +15,10 @@ pub fn draw(
     // let width = layout.area.width as usize;
 
     // Center activity
-    let activity = format!(" {} ", view.activity);
+    let activity = match &view.tally {
+        Some(tally) => format!(" {} · {tally} ", view.activity),
+        None => format!(" {} ", view.activity),
+    };
     let in_players = format!("In: {}", view.in_players);
     let out_players = format!("Out: {}", view.out_players);
 
@@ -7,7 +7,7 @@ use ratatui::{
 use crate::tui::{
     layout,
     view::MainView,
-    widgets::{lobby, table},
+    widgets::{end, lobby, stats, table},
 };
 
 pub fn draw(frame: &mut Frame, layout: &layout::Main, view: &MainView) {
@@ -25,8 +25,16 @@ pub fn draw(frame: &mut Frame, layout: &layout::Main, view: &MainView) {
             lobby::draw(frame, &lobby_layout, lobby_view).unwrap();
         }
         MainView::Table(table_view) => {
-            let table_layout = layout::Table::new(layout.content, 10);
+            let table_layout = layout::Table::new(layout.content, table_view.chairs.len());
             table::draw(frame, &table_layout, table_view).unwrap();
         }
+        MainView::Stats(stats_view) => {
+            let stats_layout = layout::Panel::new(layout.content);
+            stats::draw(frame, &stats_layout, stats_view);
+        }
+        MainView::End(end_view) => {
+            let end_layout = layout::Panel::new(layout.content);
+            end::draw(frame, &end_layout, end_view);
+        }
     }
 }
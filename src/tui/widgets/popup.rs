@@ -30,4 +30,3 @@ pub fn draw(frame: &mut Frame, layout: &layout::Events, view: &EventsView) {
         layout.content,
     );
 }
-
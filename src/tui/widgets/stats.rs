@@ -0,0 +1,78 @@
+use crate::tui::layout;
+use crate::tui::view::StatsView;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+fn percent(rate: f64) -> String {
+    format!("{:.0}%", rate * 100.0)
+}
+
+pub fn draw(frame: &mut Frame, area: &layout::Panel, view: &StatsView) {
+    // ===== Header =====
+    let header = Paragraph::new(vec![
+        Line::from(Span::styled(
+            &view.name,
+            Style::default().add_modifier(Modifier::BOLD),
+        ))
+        .alignment(Alignment::Center),
+        Line::from(format!(
+            "{} games · {} won",
+            view.games,
+            percent(view.win_rate)
+        ))
+        .alignment(Alignment::Center),
+    ])
+    .block(Block::default().borders(Borders::ALL));
+
+    frame.render_widget(header, area.header);
+
+    // ===== Body =====
+    let mut lines = vec![Line::from(Span::styled(
+        "Win rate by role",
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for role in &view.roles {
+        lines.push(Line::from(format!(
+            "  {:<10} {:>3} games  {:>4}",
+            role.role.to_string(),
+            role.games,
+            percent(role.win_rate)
+        )));
+    }
+
+    lines.push(Line::from(""));
+    let accuracy = view
+        .check_accuracy
+        .map(percent)
+        .unwrap_or_else(|| "—".to_string());
+    lines.push(Line::from(format!(
+        "Sheriff checks finding mafia: {accuracy}"
+    )));
+    lines.push(Line::from(format!(
+        "Best guess hits: {} in {} guesses",
+        view.guess_hits, view.guesses
+    )));
+    lines.push(Line::from(format!(
+        "Shot first night: {}",
+        view.shot_first_night
+    )));
+    lines.push(Line::from(format!(
+        "Warnings per game: {:.1}",
+        view.warnings_per_game
+    )));
+
+    let body =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Statistics"));
+    frame.render_widget(body, area.body);
+
+    // ===== Footer =====
+    let footer = Paragraph::new("Esc to return to the table")
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, area.footer);
+}